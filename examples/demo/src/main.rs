@@ -30,6 +30,7 @@ fn setup_scene(mut commands: Commands) {
                 max_y: 250.0,
                 min_scale: 0.5,
                 max_scale: 2.5,
+                ..default()
             },
             ..default()
         },
@@ -89,6 +90,8 @@ fn control_camera_ui(
     window.show(contexts.ctx_mut()?, |ui| {
         ui.heading("Bounds");
         ui.checkbox(&mut cam.settings.bounds.enabled, "Bounded");
+        ui.heading("Zoom");
+        ui.checkbox(&mut cam.settings.zoom_to_cursor, "Zoom to cursor");
         ui.heading("Mode");
         ui.radio_value(&mut cam.mode, OgleMode::Frozen, "Frozen");
         ui.radio_value(&mut cam.mode, OgleMode::ZoomOnly, "Zoom Only");
@@ -187,6 +190,33 @@ fn control_camera_ui(
             let random_zoom = 0.5 + random::<f32>() * 2.0; // 0.5 to 2.5
             cam.teleport(Vec3::new(random_x, random_y, random_zoom));
         }
+
+        ui.separator();
+
+        ui.heading("Animate");
+        ui.horizontal(|ui| {
+            if ui.button("Animate to Origin").clicked() {
+                cam.animate_to(Vec2::ZERO, 1.0, 1.0, EaseFunction::CubicInOut);
+            }
+            if ui.button("Animate to Target").clicked() {
+                cam.animate_to(
+                    target_transform.translation.truncate(),
+                    current_position.z,
+                    1.0,
+                    EaseFunction::CubicInOut,
+                );
+            }
+            if ui.button("Cancel").clicked() {
+                cam.cancel_animation();
+            }
+        });
+
+        ui.separator();
+
+        ui.heading("Shake");
+        if ui.button("Add Trauma").clicked() {
+            cam.add_trauma(0.5);
+        }
     });
 
     Ok(())
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+
+/// Sent when a camera animation started with [`OgleCam::animate_to`] ends.
+///
+/// [`OgleCam::animate_to`]: crate::OgleCam::animate_to
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct OgleAnimationFinished {
+    /// The camera which was animating
+    pub camera: Entity,
+    /// Whether the animation was interrupted before it completed.
+    pub interrupted: bool,
+}
+
+/// A tweened transition of the camera rig.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Animation {
+    pub from: Vec3,
+    pub to: Vec3,
+    pub duration: f32,
+    pub elapsed: f32,
+    pub easing: EaseFunction,
+}
+
+impl Animation {
+    /// Advance the animation, returning the eased rig position.
+    pub fn advance(&mut self, delta_secs: f32) -> Vec3 {
+        self.elapsed = (self.elapsed + delta_secs).min(self.duration);
+        let progress = if self.duration > 0.0 {
+            self.elapsed / self.duration
+        } else {
+            1.0
+        };
+        self.from
+            .lerp(self.to, self.easing.sample_clamped(progress))
+    }
+
+    /// Whether the animation has reached its destination.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
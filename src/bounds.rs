@@ -0,0 +1,488 @@
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "serde")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy::{camera::primitives::Aabb, prelude::*};
+
+use crate::{OgleBoundingSettings, OgleBoundingShape, OgleElasticSettings, OgleFitPolicy};
+
+/// The extent of an entity in its local space, for deriving camera bounds from it with
+/// [`OgleBoundingSettings::source`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct OgleBoundsExtent(pub Rect);
+
+/// The world-space extent of an entity, from its [`OgleBoundsExtent`] or its `Aabb`.
+pub(crate) fn entity_extent(
+    extent: Option<&OgleBoundsExtent>,
+    aabb: Option<&Aabb>,
+    transform: &GlobalTransform,
+) -> Option<Rect> {
+    let local = match (extent, aabb) {
+        (Some(extent), _) => extent.0,
+        (None, Some(aabb)) => {
+            Rect::from_center_half_size(aabb.center.truncate(), aabb.half_extents.truncate())
+        }
+        (None, None) => return None,
+    };
+
+    // Transform every corner, so rotated and scaled entities are covered
+    let corners = [
+        local.min,
+        Vec2::new(local.max.x, local.min.y),
+        local.max,
+        Vec2::new(local.min.x, local.max.y),
+    ]
+    .map(|corner| transform.transform_point(corner.extend(0.0)).truncate());
+    Some(Rect {
+        min: corners.into_iter().reduce(Vec2::min)?,
+        max: corners.into_iter().reduce(Vec2::max)?,
+    })
+}
+
+/// Rubber-band state for elastic bounds.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Elastic {
+    /// Whether input is holding the camera this frame
+    pub held: bool,
+    /// The rig position after the last correction
+    last: Option<Vec3>,
+}
+
+impl Elastic {
+    /// Let the camera overshoot the hard bounds, with increasing resistance while held, and
+    /// springing back once released.
+    pub fn correct(
+        &mut self,
+        settings: &OgleElasticSettings,
+        position: Vec3,
+        bounded: Vec3,
+        delta_secs: f32,
+    ) -> Vec3 {
+        let held = std::mem::take(&mut self.held);
+        let max_excess = Vec3::new(
+            settings.max_overscroll * position.z,
+            settings.max_overscroll * position.z,
+            settings.max_overzoom * bounded.z,
+        )
+        .max(Vec3::ZERO);
+        let excess = position - bounded;
+        let previous_excess =
+            (self.last.unwrap_or(position) - bounded).clamp(-max_excess, max_excess);
+        let decay = if settings.spring_back > 0.0 {
+            (-delta_secs / settings.spring_back).exp()
+        } else {
+            0.0
+        };
+
+        let stretch = |excess: f32, previous: f32, max: f32| {
+            if max <= 0.0 {
+                0.0
+            } else if !held {
+                // Never move further out than the spring allows
+                let limit = (previous * decay).abs();
+                excess.clamp(-limit, limit)
+            } else if excess * previous >= 0.0 && excess.abs() > previous.abs() {
+                // Resist moving further out, the more so the further out the camera is
+                let resistance = (1.0 - previous.abs() / max).max(0.0).powi(2);
+                (previous + (excess - previous) * resistance).clamp(-max, max)
+            } else {
+                excess.clamp(-max, max)
+            }
+        };
+        let corrected = bounded
+            + Vec3::new(
+                stretch(excess.x, previous_excess.x, max_excess.x),
+                stretch(excess.y, previous_excess.y, max_excess.y),
+                stretch(excess.z, previous_excess.z, max_excess.z),
+            );
+        self.last = Some(corrected);
+        corrected
+    }
+}
+
+/// The nearest camera position to `position` which keeps the viewport and scale inside the bounds.
+pub(crate) fn bounded_position(
+    bounds: &OgleBoundingSettings,
+    viewport_size: Vec2,
+    position: Vec3,
+) -> Vec3 {
+    // Bound the zoom, then the position using the scale after clamping
+    let scale_range = bounded_scale_range(bounds, viewport_size);
+    let scale = position.z.clamp(*scale_range.start(), *scale_range.end());
+    bounded_center(bounds, viewport_size, scale, position.truncate()).extend(scale)
+}
+
+/// The range of scales allowed by the bounds for a viewport of the given size.
+pub(crate) fn bounded_scale_range(
+    bounds: &OgleBoundingSettings,
+    viewport_size: Vec2,
+) -> RangeInclusive<f32> {
+    // Calculate what scale would be needed to fit the bounds area exactly in the viewport
+    let scale_to_fit = |rect: Rect| {
+        let scale_to_fit = rect.size() / viewport_size;
+        match bounds.fit {
+            // Use min instead of max to fill the viewport (may crop bounds)
+            OgleFitPolicy::Fill => scale_to_fit.min_element(),
+            OgleFitPolicy::Fit => scale_to_fit.max_element(),
+            OgleFitPolicy::FitWidth => scale_to_fit.x,
+            OgleFitPolicy::FitHeight => scale_to_fit.y,
+            OgleFitPolicy::None => f32::INFINITY,
+        }
+    };
+    let scale_to_fit_bounds = match &bounds.shape {
+        OgleBoundingShape::Rect | OgleBoundingShape::Polygon(_) => {
+            bounds_rect(bounds).map(scale_to_fit)
+        }
+        // The viewport only needs to fit inside one of the rectangles
+        OgleBoundingShape::Rects(rects) => rects.iter().copied().map(scale_to_fit).reduce(f32::max),
+    };
+    let Some(scale_to_fit_bounds) = scale_to_fit_bounds else {
+        return bounds.min_scale..=bounds.max_scale;
+    };
+
+    // The maximum allowed scale (most zoomed out) should not exceed what's needed to fit bounds
+    // But we also respect the user's min/max scale settings
+    let max_allowed_scale = bounds.max_scale.min(scale_to_fit_bounds);
+
+    // Ensure we have a valid range - if scale_to_fit_bounds is smaller than min_scale,
+    // we prioritize the bounds constraint over the user's min_scale
+    let effective_min_scale = bounds.min_scale.min(scale_to_fit_bounds);
+    effective_min_scale..=max_allowed_scale
+}
+
+/// The nearest camera center to `center` which keeps the viewport inside the bounds.
+pub(crate) fn bounded_center(
+    bounds: &OgleBoundingSettings,
+    viewport_size: Vec2,
+    scale: f32,
+    center: Vec2,
+) -> Vec2 {
+    // Calculate half sizes of the viewport in world units
+    let half_size = viewport_size * scale * 0.5;
+
+    match &bounds.shape {
+        OgleBoundingShape::Rect => bounds_rect(bounds)
+            .map(|rect| rect_center(rect, half_size, center, bounds.anchor))
+            .unwrap_or(center),
+        // Only rectangles the viewport fits in can hold it, otherwise it would overflow the union
+        OgleBoundingShape::Rects(rects) => rects
+            .iter()
+            .filter(|rect| rect.size().cmpge(half_size * 2.0).all())
+            .map(|rect| rect_center(*rect, half_size, center, bounds.anchor))
+            .min_by(|a, b| {
+                a.distance_squared(center)
+                    .total_cmp(&b.distance_squared(center))
+            })
+            // When the viewport fits in none of them, place it by the anchor in their extent
+            .or_else(|| {
+                bounds_rect(bounds).map(|rect| rect_center(rect, half_size, center, bounds.anchor))
+            })
+            .unwrap_or(center),
+        OgleBoundingShape::Polygon(points) => polygon_center(points, half_size, center)
+            // When the viewport fits nowhere inside the polygon, fall back to its extent
+            .or_else(|| {
+                bounds_rect(bounds).map(|rect| rect_center(rect, half_size, center, bounds.anchor))
+            })
+            .unwrap_or(center),
+    }
+}
+
+/// The axis-aligned extent of the bounds, or `None` if the shape is empty.
+pub(crate) fn bounds_rect(bounds: &OgleBoundingSettings) -> Option<Rect> {
+    match &bounds.shape {
+        OgleBoundingShape::Rect => Some(Rect {
+            min: Vec2::new(bounds.min_x, bounds.min_y),
+            max: Vec2::new(bounds.max_x, bounds.max_y),
+        }),
+        OgleBoundingShape::Rects(rects) => rects.iter().copied().reduce(|a, b| a.union(b)),
+        OgleBoundingShape::Polygon(points) if points.len() >= 3 => Some(Rect {
+            min: points.iter().copied().reduce(Vec2::min)?,
+            max: points.iter().copied().reduce(Vec2::max)?,
+        }),
+        OgleBoundingShape::Polygon(_) => None,
+    }
+}
+
+/// The nearest center to `center` which keeps a viewport inside a rectangle.
+fn rect_center(rect: Rect, half_size: Vec2, center: Vec2, anchor: Vec2) -> Vec2 {
+    // The camera center must stay within these bounds to keep the entire viewport within the
+    // target area
+    let effective_min = rect.min + half_size;
+    let effective_max = rect.max - half_size;
+
+    // Only apply bounds if they make sense (i.e., the bounded area is larger than the viewport),
+    // otherwise place the bounds in the viewport by the anchor
+    let anchor = anchor.clamp(Vec2::splat(-0.5), Vec2::splat(0.5)) + 0.5;
+    let x = if effective_min.x <= effective_max.x {
+        center.x.clamp(effective_min.x, effective_max.x)
+    } else {
+        effective_min.x.lerp(effective_max.x, anchor.x)
+    };
+    let y = if effective_min.y <= effective_max.y {
+        center.y.clamp(effective_min.y, effective_max.y)
+    } else {
+        effective_min.y.lerp(effective_max.y, anchor.y)
+    };
+
+    Vec2::new(x, y)
+}
+
+/// The nearest center to `center` which keeps a viewport inside a polygon, or `None` if the
+/// viewport fits nowhere inside it.
+///
+/// This is O(N) for N polygon points while the viewport is inside. Against an edge, it is O(N)
+/// plus O(K²) for the K sweep sides nearer than the nearest valid projection, which are few. If
+/// the viewport fits nowhere, but isn't larger than the polygon's extent, it is O(N³).
+fn polygon_center(points: &[Vec2], half_size: Vec2, center: Vec2) -> Option<Vec2> {
+    if points.len() < 3 {
+        return None;
+    }
+    if viewport_in_polygon(points, half_size, center) {
+        return Some(center);
+    }
+
+    // A viewport larger than the polygon's extent fits nowhere inside it
+    let extent_size =
+        points.iter().copied().reduce(Vec2::max)? - points.iter().copied().reduce(Vec2::min)?;
+    if extent_size.cmplt(half_size * 2.0).any() {
+        return None;
+    }
+
+    // The viewport overlaps a polygon edge while its center is inside the sweep of the viewport
+    // along that edge. The nearest valid center lies on the outline of those sweeps, either
+    // projected onto one of their sides, or at a corner where sides meet.
+    let corners = [
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(-1.0, 1.0),
+    ]
+    .map(|corner| corner * half_size);
+    let mut sides = Vec::with_capacity(points.len() * 12);
+    for (a, b) in polygon_edges(points) {
+        for (i, corner) in corners.iter().enumerate() {
+            let next_corner = corners[(i + 1) % corners.len()];
+            sides.push((a + *corner, b + *corner));
+            sides.push((a + *corner, a + next_corner));
+            sides.push((b + *corner, b + next_corner));
+        }
+    }
+
+    // The nearest valid projection onto a side
+    let mut projections = sides
+        .iter()
+        .map(|(a, b)| {
+            let projection = closest_point_on_segment(*a, *b, center);
+            (projection.distance_squared(center), projection, (*a, *b))
+        })
+        .collect::<Vec<_>>();
+    projections.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let projected = projections
+        .iter()
+        .find(|(_, projection, _)| viewport_in_polygon(points, half_size, *projection))
+        .map(|(distance, projection, _)| (*distance, *projection));
+    let reach = projected.map_or(f32::INFINITY, |(distance, _)| distance);
+
+    // A nearer corner can only be on sides nearer than that projection
+    let near_sides = projections
+        .iter()
+        .take_while(|(distance, ..)| *distance < reach)
+        .map(|(.., side)| *side)
+        .collect::<Vec<_>>();
+    let mut candidates = near_sides
+        .iter()
+        .flat_map(|(a, b)| [*a, *b])
+        .collect::<Vec<_>>();
+    for (i, side) in near_sides.iter().enumerate() {
+        candidates.extend(
+            near_sides[i + 1..]
+                .iter()
+                .filter_map(|other| segment_intersection(*side, *other)),
+        );
+    }
+    candidates.retain(|candidate| candidate.distance_squared(center) < reach);
+    candidates.sort_by(|a, b| {
+        a.distance_squared(center)
+            .total_cmp(&b.distance_squared(center))
+    });
+    candidates
+        .into_iter()
+        .find(|candidate| viewport_in_polygon(points, half_size, *candidate))
+        .or(projected.map(|(_, projection)| projection))
+}
+
+/// Whether a viewport lies entirely inside a polygon.
+fn viewport_in_polygon(points: &[Vec2], half_size: Vec2, center: Vec2) -> bool {
+    // Candidates sit exactly on an edge, so touching an edge must not count as overlapping it
+    const TOLERANCE: f32 = 1e-4;
+    let viewport = Rect::from_center_half_size(center, half_size * (1.0 - TOLERANCE));
+    point_in_polygon(points, center)
+        && polygon_edges(points).all(|(a, b)| !segment_overlaps_rect(a, b, viewport))
+}
+
+/// The closed edges of a polygon.
+fn polygon_edges(points: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+}
+
+/// Whether a point is inside a polygon, by the even-odd rule.
+fn point_in_polygon(points: &[Vec2], point: Vec2) -> bool {
+    polygon_edges(points)
+        .filter(|(a, b)| {
+            (a.y > point.y) != (b.y > point.y)
+                && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        })
+        .count()
+        % 2
+        == 1
+}
+
+/// Whether a line segment overlaps a rectangle, by Liang-Barsky clipping.
+fn segment_overlaps_rect(a: Vec2, b: Vec2, rect: Rect) -> bool {
+    let delta = b - a;
+    let mut entry = 0.0_f32;
+    let mut exit = 1.0_f32;
+    for (p, q) in [
+        (-delta.x, a.x - rect.min.x),
+        (delta.x, rect.max.x - a.x),
+        (-delta.y, a.y - rect.min.y),
+        (delta.y, rect.max.y - a.y),
+    ] {
+        if p == 0.0 {
+            // Parallel to this side, and outside of it
+            if q < 0.0 {
+                return false;
+            }
+        } else if p < 0.0 {
+            entry = entry.max(q / p);
+        } else {
+            exit = exit.min(q / p);
+        }
+    }
+    entry <= exit
+}
+
+/// The point on a line segment closest to `point`.
+fn closest_point_on_segment(a: Vec2, b: Vec2, point: Vec2) -> Vec2 {
+    let delta = b - a;
+    let length_squared = delta.length_squared();
+    if length_squared == 0.0 {
+        return a;
+    }
+    a + delta * ((point - a).dot(delta) / length_squared).clamp(0.0, 1.0)
+}
+
+/// The intersection of two line segments, if they cross.
+fn segment_intersection((a, b): (Vec2, Vec2), (c, d): (Vec2, Vec2)) -> Option<Vec2> {
+    let r = b - a;
+    let s = d - c;
+    let denominator = r.perp_dot(s);
+    if denominator.abs() <= f32::EPSILON {
+        return None;
+    }
+    let t = (c - a).perp_dot(s) / denominator;
+    let u = (c - a).perp_dot(r) / denominator;
+    ((0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u)).then(|| a + r * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.distance(expected) < 0.01,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn convex_polygon() {
+        let diamond = [
+            Vec2::new(0.0, -1000.0),
+            Vec2::new(1000.0, 0.0),
+            Vec2::new(0.0, 1000.0),
+            Vec2::new(-1000.0, 0.0),
+        ];
+        let half_size = Vec2::splat(100.0);
+
+        // Inside, the center is kept
+        let center = Vec2::new(100.0, -200.0);
+        assert_eq!(polygon_center(&diamond, half_size, center), Some(center));
+
+        // Outside, the viewport corners touch the slanted edges
+        let bounded = polygon_center(&diamond, half_size, Vec2::new(2000.0, 0.0)).unwrap();
+        assert_near(bounded, Vec2::new(800.0, 0.0));
+        assert!(viewport_in_polygon(&diamond, half_size, bounded));
+    }
+
+    #[test]
+    fn concave_polygon() {
+        let l_shape = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(2000.0, 0.0),
+            Vec2::new(2000.0, 1000.0),
+            Vec2::new(1000.0, 1000.0),
+            Vec2::new(1000.0, 2000.0),
+            Vec2::new(0.0, 2000.0),
+        ];
+        let half_size = Vec2::splat(200.0);
+
+        // In the notch, the nearest valid center is below or left of it
+        let center = Vec2::new(1500.0, 1500.0);
+        let bounded = polygon_center(&l_shape, half_size, center).unwrap();
+        assert!((bounded.distance(center) - 700.0).abs() < 0.01, "{bounded}");
+        assert!(viewport_in_polygon(&l_shape, half_size, bounded));
+
+        // Inside the polygon, but with the viewport over the reflex corner
+        let center = Vec2::new(900.0, 900.0);
+        let bounded = polygon_center(&l_shape, half_size, center).unwrap();
+        assert!((bounded.distance(center) - 100.0).abs() < 0.01, "{bounded}");
+        assert!(viewport_in_polygon(&l_shape, half_size, bounded));
+    }
+
+    #[test]
+    fn polygon_too_small_for_viewport() {
+        let triangle = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1000.0, 0.0),
+            Vec2::new(0.0, 1000.0),
+        ];
+        let half_size = Vec2::splat(400.0);
+        assert_eq!(polygon_center(&triangle, half_size, Vec2::ZERO), None);
+
+        // The bounds fall back to the polygon's extent
+        let bounds = OgleBoundingSettings {
+            enabled: true,
+            shape: OgleBoundingShape::Polygon(triangle),
+            ..default()
+        };
+        let bounded = bounded_center(&bounds, Vec2::ONE, 800.0, Vec2::new(3000.0, 0.0));
+        assert_near(bounded, Vec2::new(600.0, 400.0));
+    }
+
+    #[test]
+    fn rects_only_hold_viewports_that_fit() {
+        // A corridor too short for the viewport, next to a room it fits in
+        let corridor = Rect::new(-100.0, -100.0, 1000.0, 100.0);
+        let room = Rect::new(1000.0, -1000.0, 3000.0, 1000.0);
+        let bounds = OgleBoundingSettings {
+            enabled: true,
+            shape: OgleBoundingShape::Rects(vec![corridor, room]),
+            ..default()
+        };
+
+        let bounded = bounded_center(&bounds, Vec2::splat(600.0), 1.0, Vec2::new(500.0, 0.0));
+        assert_near(bounded, Vec2::new(1300.0, 0.0));
+
+        // Fitting in none of them, the viewport is anchored in their extent
+        let bounded = bounded_center(&bounds, Vec2::splat(4000.0), 1.0, Vec2::new(500.0, 0.0));
+        assert_near(bounded, Vec2::new(1450.0, 0.0));
+    }
+}
@@ -1,18 +1,23 @@
-use bevy::prelude::*;
+use bevy::{ecs::schedule::InternedScheduleLabel, prelude::*};
 
 use crate::OgleSystems;
 
 #[derive(Resource, Deref, DerefMut, PartialEq, Eq, Default)]
 struct EguiWantsFocus(bool);
 
-pub(crate) struct EguiPanCamPlugin;
+pub(crate) struct EguiPanCamPlugin {
+    pub schedule: InternedScheduleLabel,
+}
 
 impl Plugin for EguiPanCamPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EguiWantsFocus>()
-            .add_systems(PostUpdate, check_egui_wants_focus)
+            .add_systems(
+                PostUpdate,
+                check_egui_wants_focus.before(OgleSystems::Input),
+            )
             .configure_sets(
-                Update,
+                self.schedule,
                 OgleSystems::Input.run_if(resource_equals(EguiWantsFocus(false))),
             );
     }
@@ -0,0 +1,32 @@
+use bevy::prelude::*;
+
+use crate::OgleMode;
+
+/// Sent when a camera comes to rest at its destination.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct OgleArrived {
+    /// The camera which arrived
+    pub camera: Entity,
+    /// The position of the camera rig, where `z` is the scale
+    pub position: Vec3,
+}
+
+/// Sent when the [`OgleMode`] of a camera changes.
+#[derive(Message, Debug, Clone, PartialEq, Eq)]
+pub struct OgleModeChanged {
+    /// The camera whose mode changed
+    pub camera: Entity,
+    /// The mode before the change
+    pub previous: OgleMode,
+    /// The mode after the change
+    pub current: OgleMode,
+}
+
+/// Sent when the entity a camera is targeting no longer exists.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OgleTargetLost {
+    /// The camera which lost its target
+    pub camera: Entity,
+    /// The missing target entity
+    pub target: Entity,
+}
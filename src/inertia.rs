@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::OgleInertiaSettings;
+
+/// Tracks drag velocity in pancam mode, to keep the camera moving after a drag is released.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Inertia {
+    /// Recent camera movements while dragging, in world units, and the frame time they took
+    history: VecDeque<(Vec2, f32)>,
+    /// The current inertial velocity, in world units per second
+    velocity: Vec2,
+    dragging: bool,
+}
+
+impl Inertia {
+    /// Record a frame of dragging, where `movement` is the resulting camera movement.
+    pub fn drag(&mut self, settings: &OgleInertiaSettings, movement: Vec2, delta_secs: f32) {
+        if !self.dragging {
+            // A new grab cancels any inertial movement
+            self.history.clear();
+            self.velocity = Vec2::ZERO;
+            self.dragging = true;
+        }
+        self.history.push_back((movement, delta_secs));
+        while self.history.len() > settings.samples.max(1) {
+            self.history.pop_front();
+        }
+    }
+
+    /// Release a drag, if there was one, starting inertial movement.
+    pub fn release(&mut self, settings: &OgleInertiaSettings) {
+        if !self.dragging {
+            return;
+        }
+        self.dragging = false;
+
+        let (movement, duration) = self
+            .history
+            .drain(..)
+            .fold((Vec2::ZERO, 0.0), |(movement, duration), (m, d)| {
+                (movement + m, duration + d)
+            });
+        self.velocity = if settings.enabled && duration > 0.0 {
+            movement / duration
+        } else {
+            Vec2::ZERO
+        };
+    }
+
+    /// Advance inertial movement, returning the camera movement for this frame.
+    pub fn advance(&mut self, settings: &OgleInertiaSettings, scale: f32, delta_secs: f32) -> Vec2 {
+        if self.dragging || self.velocity == Vec2::ZERO {
+            return Vec2::ZERO;
+        }
+        let movement = self.velocity * delta_secs;
+        self.velocity *= (-settings.friction * delta_secs).exp();
+
+        // Stop once the camera is crawling across the screen
+        if self.velocity.length() / scale < settings.min_speed {
+            self.velocity = Vec2::ZERO;
+        }
+        movement
+    }
+
+    /// React to the camera hitting the bounds along the given axes, stopping or bouncing.
+    pub fn hit_bounds(&mut self, settings: &OgleInertiaSettings, hit: BVec2) {
+        let response = if settings.bounce {
+            -settings.restitution
+        } else {
+            0.0
+        };
+        if hit.x {
+            self.velocity.x *= response;
+        }
+        if hit.y {
+            self.velocity.y *= response;
+        }
+    }
+
+    /// Whether the camera is moving under inertia.
+    pub fn is_moving(&self) -> bool {
+        self.velocity != Vec2::ZERO
+    }
+
+    /// Stop all inertial movement and forget the drag.
+    pub fn stop(&mut self) {
+        *self = Self::default();
+    }
+}
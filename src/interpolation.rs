@@ -0,0 +1,71 @@
+#[cfg(feature = "serde")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// Interpolates an entity moved in `FixedUpdate` when cameras follow it.
+///
+/// The entity's transform is recorded at the end of every fixed timestep. Cameras then follow
+/// the position between the last two timesteps, by how far the frame is into the next timestep,
+/// so they move smoothly at any fixed tick rate. This is for cameras outside `FixedUpdate`.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Component, Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct OgleInterpolated {
+    /// The transform at the end of the timestep before the last
+    previous: Option<Transform>,
+    /// The transform at the end of the last timestep
+    current: Option<Transform>,
+}
+
+impl OgleInterpolated {
+    /// Record the transform at the end of a fixed timestep.
+    pub(crate) fn record(&mut self, transform: Transform) {
+        self.previous = self.current.or(Some(transform));
+        self.current = Some(transform);
+    }
+
+    /// Forget the recorded transforms, such as after teleporting the entity.
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.current = None;
+    }
+
+    /// The transform between the last two fixed timesteps, if any were recorded.
+    pub fn interpolate(&self, overstep_fraction: f32) -> Option<Transform> {
+        let (previous, current) = (self.previous?, self.current?);
+        Some(Transform {
+            translation: previous
+                .translation
+                .lerp(current.translation, overstep_fraction),
+            rotation: previous.rotation.slerp(current.rotation, overstep_fraction),
+            scale: previous.scale.lerp(current.scale, overstep_fraction),
+        })
+    }
+}
+
+/// The transforms of camera targets, interpolated for [`OgleInterpolated`] entities.
+#[derive(SystemParam)]
+pub(crate) struct TargetTransforms<'w, 's> {
+    transforms: Query<'w, 's, (&'static Transform, Option<&'static OgleInterpolated>)>,
+    fixed_time: Res<'w, Time<Fixed>>,
+}
+
+impl TargetTransforms<'_, '_> {
+    /// The transform of a target, or `None` if it has no transform.
+    pub fn get(&self, entity: Entity) -> Option<Transform> {
+        let (transform, interpolated) = self.transforms.get(entity).ok()?;
+        Some(
+            interpolated
+                .and_then(|interpolated| {
+                    interpolated.interpolate(self.fixed_time.overstep_fraction())
+                })
+                .unwrap_or(*transform),
+        )
+    }
+
+    /// Whether a target has a transform.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.transforms.contains(entity)
+    }
+}
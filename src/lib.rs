@@ -1,10 +1,45 @@
+use bevy::ecs::{
+    entity::{EntityMapper, MapEntities},
+    reflect::ReflectMapEntities,
+};
 use bevy::prelude::*;
+#[cfg(feature = "serde")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
 use dolly::prelude::*;
+use rotation::OgleRotation;
+use smoothing::OgleSmooth;
 
+mod bounds;
+pub use bounds::OgleBoundsExtent;
+mod interpolation;
+pub use interpolation::OgleInterpolated;
+mod inertia;
+mod rotation;
+mod smoothing;
 mod systems;
 
+mod viewport;
+pub use viewport::OgleViewports;
+
+mod shake;
+pub use shake::OgleTrauma;
+
+mod animation;
+pub use animation::OgleAnimationFinished;
+
+mod zone;
+pub use zone::OgleCameraZone;
+
+#[cfg(feature = "profile")]
+mod profile;
+#[cfg(feature = "profile")]
+pub use profile::{OgleCamProfile, OgleProfile, OgleProfileLoader, OgleProfileLoaderError};
+
+mod events;
+pub use events::{OgleArrived, OgleModeChanged, OgleTargetLost};
+
 mod plugin;
-pub use plugin::OglePlugin;
+pub use plugin::{OglePlugin, OgleScheduledPlugin};
 
 #[cfg(feature = "internal_bevy_egui")]
 mod egui_support;
@@ -16,32 +51,122 @@ pub enum OgleSystems {
     Input,
     Correction,
     Commit,
+    Effects,
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Reflect)]
+#[reflect(Component, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
 #[require(Camera2d)]
 pub struct OgleCam {
+    #[entities]
     pub settings: OgleSettings,
+    #[entities]
     pub target: OgleTarget,
     pub mode: OgleMode,
+    /// The rig state as of the last commit, so a camera loaded from a scene restores its view
+    rig_snapshot: OgleRigSnapshot,
+    #[reflect(ignore, default = "default_rig")]
+    #[cfg_attr(feature = "serde", serde(skip, default = "default_rig"))]
     rig: CameraRig,
+    /// Whether the rig is up to date, rather than waiting to be restored from `rig_snapshot`
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    rig_restored: bool,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    follow_offset: Vec2,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    look_ahead: LookAhead,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    shake: shake::Shake,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    animation: Option<animation::Animation>,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    animation_interrupted: bool,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    last_mode: OgleMode,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    arrived: bool,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    target_lost: bool,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    inertia: inertia::Inertia,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    elastic: bounds::Elastic,
+    #[reflect(ignore)]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    zone: zone::ZoneState,
+}
+
+/// The state of a camera rig, for saving and restoring the exact camera view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct OgleRigSnapshot {
+    /// The position and scale the rig is moving towards
+    pub position: Vec3,
+    /// The position and scale currently shown on screen
+    pub committed_position: Vec3,
+    /// The rotation the rig is turning towards, in radians
+    pub rotation: f32,
+    /// The rotation currently shown on screen, in radians
+    pub committed_rotation: f32,
+    /// The offset applied on top of the target while following
+    pub follow_offset: Vec2,
+}
+
+fn build_rig(smoothing: OgleSmoothingSettings) -> CameraRig {
+    CameraRig::builder()
+        .with(Position::new(mint::Point3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        }))
+        .with(OgleRotation::new(0.0))
+        .with(OgleSmooth::new(smoothing))
+        .build()
+}
+
+fn default_rig() -> CameraRig {
+    build_rig(Default::default())
 }
 
 impl OgleCam {
     pub fn new(settings: OgleSettings, target: OgleTarget, mode: OgleMode) -> Self {
-        Self {
+        let rig = build_rig(settings.smoothing);
+        let mut cam = Self {
             settings,
             target,
+            last_mode: mode.clone(),
             mode,
-            rig: CameraRig::builder()
-                .with(Position::new(mint::Point3 {
-                    x: 0.0,
-                    y: 0.0,
-                    z: 1.0,
-                }))
-                .with(Smooth::new_position(1.5).predictive(false))
-                .build(),
-        }
+            rig_snapshot: Default::default(),
+            rig,
+            rig_restored: true,
+            follow_offset: Vec2::ZERO,
+            look_ahead: Default::default(),
+            shake: Default::default(),
+            animation: None,
+            animation_interrupted: false,
+            arrived: true,
+            target_lost: false,
+            inertia: Default::default(),
+            elastic: Default::default(),
+            zone: Default::default(),
+        };
+        cam.rig_snapshot = cam.snapshot();
+        cam
     }
 }
 
@@ -62,29 +187,305 @@ impl OgleCam {
         }
     }
 
+    /// Get the committed position of the camera rig, which is what is currently shown on screen.
+    ///
+    /// This lags behind [`OgleCam::position`] while the camera is smoothing towards it.
+    pub fn committed_position(&self) -> Vec3 {
+        let position = self.rig.final_transform.position;
+        Vec3 {
+            x: position.x,
+            y: position.y,
+            z: position.z,
+        }
+    }
+
+    /// Get the target rotation of the camera rig, counterclockwise in radians.
+    pub fn rotation(&self) -> f32 {
+        self.rig.driver::<OgleRotation>().angle
+    }
+
+    /// Set the target rotation of the camera rig, counterclockwise in radians.
+    ///
+    /// The camera turns towards it with the rotation smoothing.
+    pub fn set_rotation(&mut self, angle: f32) {
+        self.rig.driver_mut::<OgleRotation>().angle = angle;
+    }
+
+    /// Get the committed rotation of the camera rig, which is what is currently shown on screen.
+    pub fn committed_rotation(&self) -> f32 {
+        rotation::quat_to_angle(self.rig.final_transform.rotation)
+    }
+
+    /// Convert a position in a viewport of the given logical size, in logical pixels from its top
+    /// left corner, to a world position.
+    pub fn viewport_to_world(&self, viewport_size: Vec2, viewport_position: Vec2) -> Vec2 {
+        let committed = self.committed_position();
+        let offset = viewport_position - viewport_size * 0.5;
+        let offset =
+            Vec2::from_angle(self.committed_rotation()).rotate(Vec2::new(offset.x, -offset.y));
+        committed.truncate() + offset * committed.z
+    }
+
+    /// Convert a world position to a position in a viewport of the given logical size, in
+    /// logical pixels from its top left corner.
+    pub fn world_to_viewport(&self, viewport_size: Vec2, world_position: Vec2) -> Vec2 {
+        let committed = self.committed_position();
+        let offset = (world_position - committed.truncate()) / committed.z;
+        let offset = Vec2::from_angle(-self.committed_rotation()).rotate(offset);
+        viewport_size * 0.5 + Vec2::new(offset.x, -offset.y)
+    }
+
+    /// Get the world-space rect visible in a viewport of the given logical size.
+    ///
+    /// While the camera is rotated, this is the box around the rotated viewport.
+    pub fn visible_rect(&self, viewport_size: Vec2) -> Rect {
+        let committed = self.committed_position();
+        Rect::from_center_size(
+            committed.truncate(),
+            rotation::rotated_size(viewport_size * committed.z, self.committed_rotation()),
+        )
+    }
+
+    /// The size of the box around the viewport at the target rotation, for bounding.
+    pub(crate) fn bounding_viewport_size(&self, viewport_size: Vec2) -> Vec2 {
+        rotation::rotated_size(viewport_size, self.rotation())
+    }
+
     /// Instantly teleport the camera to a new position.
+    ///
+    /// The configured smoothing is kept, and applies to movement after the teleport. Any running
+    /// animation is interrupted.
     pub fn teleport(&mut self, position: Vec3) {
-        self.rig = CameraRig::builder()
-            .with(Position::new(mint::Point3 {
-                x: position.x,
-                y: position.y,
-                z: position.z,
-            }))
-            .with(Smooth::new_position(1.5).predictive(false))
-            .build();
+        self.cancel_animation();
+        self.rig.driver_mut::<Position>().position = mint::Point3 {
+            x: position.x,
+            y: position.y,
+            z: position.z,
+        };
+        self.rig.driver_mut::<OgleSmooth>().reset();
+        self.rig.update(0.0);
+    }
+
+    /// Animate the camera to a new position and zoom over `duration` seconds.
+    ///
+    /// Following and user input are suspended while animating, but bounds are still respected.
+    /// Any running animation is interrupted. An [`OgleAnimationFinished`] message is sent when
+    /// the animation ends.
+    pub fn animate_to(&mut self, position: Vec2, zoom: f32, duration: f32, easing: EaseFunction) {
+        self.cancel_animation();
+        let from = self.rig.final_transform.position;
+        self.animation = Some(animation::Animation {
+            from: Vec3::new(from.x, from.y, from.z),
+            to: position.extend(zoom),
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            easing,
+        });
+    }
+
+    /// Stop any running animation, leaving the camera where it currently is.
+    pub fn cancel_animation(&mut self) {
+        if self.animation.take().is_some() {
+            self.animation_interrupted = true;
+        }
+    }
+
+    /// Whether the camera is running an animation started by [`OgleCam::animate_to`].
+    pub fn is_animating(&self) -> bool {
+        self.animation.is_some()
+    }
+
+    /// Get the offset applied on top of the target while following.
+    ///
+    /// This is accumulated by cursor-anchored zooming in [`OgleMode::Normal`].
+    pub fn follow_offset(&self) -> Vec2 {
+        self.follow_offset
+    }
+
+    /// Set the offset applied on top of the target while following.
+    pub fn set_follow_offset(&mut self, offset: Vec2) {
+        self.follow_offset = offset;
+    }
+
+    /// Get the current shake trauma, between `0.0` and `1.0`.
+    pub fn trauma(&self) -> f32 {
+        self.shake.trauma
+    }
+
+    /// Add trauma to shake the camera. Trauma is capped at `1.0`.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.shake.trauma = (self.shake.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// Take a snapshot of the camera rig, to restore the exact camera view later.
+    pub fn snapshot(&self) -> OgleRigSnapshot {
+        OgleRigSnapshot {
+            position: self.position(),
+            committed_position: self.committed_position(),
+            rotation: self.rotation(),
+            committed_rotation: self.committed_rotation(),
+            follow_offset: self.follow_offset,
+        }
+    }
+
+    /// Restore the camera rig from a snapshot, showing the exact camera view it was taken with.
+    ///
+    /// Any running animation is interrupted.
+    pub fn restore(&mut self, snapshot: &OgleRigSnapshot) {
+        self.cancel_animation();
+        self.rig.driver_mut::<Position>().position = mint::Point3 {
+            x: snapshot.position.x,
+            y: snapshot.position.y,
+            z: snapshot.position.z,
+        };
+        self.set_rotation(snapshot.rotation);
+        self.follow_offset = snapshot.follow_offset;
+        self.rig
+            .driver_mut::<OgleSmooth>()
+            .restore(snapshot.committed_position, snapshot.committed_rotation);
+        self.rig.update(0.0);
+        self.rig_snapshot = *snapshot;
+        self.rig_restored = true;
+    }
+
+    /// Get the [`OgleCameraZone`] entity containing the target, if any.
+    pub fn active_zone(&self) -> Option<Entity> {
+        self.zone.active
+    }
+
+    /// Get the bounds in effect, which are those of the active [`OgleCameraZone`], if any.
+    pub fn active_bounds(&self) -> &OgleBoundingSettings {
+        self.zone.bounds.as_ref().unwrap_or(&self.settings.bounds)
     }
 }
 
-#[derive(Clone, PartialEq, Debug, Default)]
+/// Tracks the velocity of a followed target to offset the camera ahead of it.
+#[derive(Debug, Clone, Default)]
+struct LookAhead {
+    /// The target being tracked, so switching targets doesn't count as movement
+    followed: OgleTarget,
+    last_target: Option<Vec2>,
+    offset: Vec2,
+}
+
+impl LookAhead {
+    /// Whether a target is being tracked.
+    fn is_tracking(&self) -> bool {
+        self.last_target.is_some() || self.offset != Vec2::ZERO
+    }
+
+    /// Forget the tracked target, so following resumes without a velocity spike.
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Record the latest position of the followed target, returning the look-ahead offset.
+    fn update(
+        &mut self,
+        settings: &OgleLookAheadSettings,
+        followed: &OgleTarget,
+        target: Vec2,
+        delta_secs: f32,
+    ) -> Vec2 {
+        if self.followed != *followed {
+            self.reset();
+            self.followed = followed.clone();
+        }
+        let last_target = self.last_target.replace(target);
+        if !settings.enabled {
+            self.offset = Vec2::ZERO;
+            return self.offset;
+        }
+        let Some(last_target) = last_target.filter(|_| delta_secs > 0.0) else {
+            return self.offset;
+        };
+
+        let velocity = (target - last_target) / delta_secs;
+        let gain = Vec2::new(settings.horizontal_gain, settings.vertical_gain);
+        let desired_offset = (velocity * gain).clamp_length_max(settings.max_distance);
+
+        // Ease towards the new offset, so direction changes don't snap the camera
+        let blend = if settings.smoothing > 0.0 {
+            1.0 - (-delta_secs / settings.smoothing).exp()
+        } else {
+            1.0
+        };
+        self.offset = self.offset.lerp(desired_offset, blend);
+        self.offset
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
 pub enum OgleTarget {
     Position(Vec2),
     Entity(Entity),
     EntityWithOffset((Entity, Vec2)),
+    /// Frame a group of entities, zooming so that every member fits in the viewport.
+    Group(OgleGroup),
     #[default]
     None,
 }
 
-#[derive(Clone, PartialEq, Eq, Hash, Debug, Default)]
+/// A group of entities framed together by [`OgleTarget::Group`].
+#[derive(Clone, PartialEq, Debug, Default, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct OgleGroup {
+    /// The entities of the group, and their weight towards the group center
+    pub members: Vec<(Entity, f32)>,
+    /// Padding kept between the members and the viewport edges, in world units
+    pub padding: Vec2,
+}
+
+impl MapEntities for OgleTarget {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        match self {
+            OgleTarget::Entity(entity) | OgleTarget::EntityWithOffset((entity, _)) => {
+                entity.map_entities(entity_mapper);
+            }
+            OgleTarget::Group(group) => group.map_entities(entity_mapper),
+            OgleTarget::Position(_) | OgleTarget::None => {}
+        }
+    }
+}
+
+impl MapEntities for OgleGroup {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        for (entity, _) in &mut self.members {
+            entity.map_entities(entity_mapper);
+        }
+    }
+}
+
+impl OgleGroup {
+    /// Create a group where every entity is weighted equally.
+    pub fn new(entities: impl IntoIterator<Item = Entity>) -> Self {
+        Self::weighted(entities.into_iter().map(|entity| (entity, 1.0)))
+    }
+
+    /// Create a group of entities with their weights towards the group center.
+    pub fn weighted(members: impl IntoIterator<Item = (Entity, f32)>) -> Self {
+        Self {
+            members: members.into_iter().collect(),
+            padding: Vec2::ZERO,
+        }
+    }
+
+    /// Set the padding kept between the members and the viewport edges.
+    pub fn with_padding(mut self, padding: Vec2) -> Self {
+        self.padding = padding;
+        self
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
 pub enum OgleMode {
     /// The camera will not respond to user input.
     #[default]
@@ -99,12 +500,34 @@ pub enum OgleMode {
     Pancam,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Reflect, MapEntities)]
+#[reflect(Debug, Clone, PartialEq, Default, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OgleSettings {
     /// Zoom sensitivity
     pub zoom_sensitivity: f32,
+    /// Whether zooming is anchored to the world position under the cursor, rather than the
+    /// center of the screen.
+    pub zoom_to_cursor: bool,
+    /// Keyboard zoom and discrete zoom levels
+    pub zoom: OgleZoomSettings,
+    /// Whether the camera follows the rotation of an entity target
+    pub follow_rotation: bool,
+    /// Framing of the followed target
+    pub framing: OgleFramingSettings,
+    /// Look-ahead when following entities
+    pub look_ahead: OgleLookAheadSettings,
+    /// Smoothing of camera movement and zoom
+    pub smoothing: OgleSmoothingSettings,
+    /// Screen shake
+    pub shake: OgleShakeSettings,
     /// Bounds for the camera
+    #[entities]
     pub bounds: OgleBoundingSettings,
+    /// Switching bounds with the [`OgleCameraZone`] containing the target
+    pub zones: OgleZoneSettings,
     /// Settings for pancam mode
     pub pancam: OglePancamSettings,
 }
@@ -113,13 +536,195 @@ impl Default for OgleSettings {
     fn default() -> Self {
         Self {
             zoom_sensitivity: 100.0,
+            zoom_to_cursor: false,
+            zoom: Default::default(),
+            follow_rotation: false,
+            framing: Default::default(),
+            look_ahead: Default::default(),
+            smoothing: Default::default(),
+            shake: Default::default(),
             bounds: Default::default(),
+            zones: Default::default(),
             pancam: Default::default(),
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+/// Keyboard zoom controls and discrete zoom levels.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleZoomSettings {
+    /// Keyboard keys for zooming in
+    pub zoom_in_keys: Vec<KeyCode>,
+    /// Keyboard keys for zooming out
+    pub zoom_out_keys: Vec<KeyCode>,
+    /// Zoom speed while a zoom key is held, as a factor of scale per second
+    pub keyboard_speed: f32,
+    /// Discrete camera scales that the mouse wheel and zoom keys step between.
+    ///
+    /// When empty, zooming is continuous. Steps are eased by the zoom smoothing.
+    pub levels: Vec<f32>,
+}
+
+impl Default for OgleZoomSettings {
+    fn default() -> Self {
+        const ZOOM_IN_KEYS: [KeyCode; 3] = [KeyCode::Equal, KeyCode::NumpadAdd, KeyCode::PageUp];
+        const ZOOM_OUT_KEYS: [KeyCode; 3] =
+            [KeyCode::Minus, KeyCode::NumpadSubtract, KeyCode::PageDown];
+        Self {
+            zoom_in_keys: ZOOM_IN_KEYS.to_vec(),
+            zoom_out_keys: ZOOM_OUT_KEYS.to_vec(),
+            keyboard_speed: 2.0,
+            levels: Vec::new(),
+        }
+    }
+}
+
+/// Dead-zone and soft-zone framing of the followed target.
+///
+/// Zones are rectangles centered on the camera. While the target is inside the dead zone, the
+/// camera does not move. Inside the soft zone, the camera catches up with damping, and the target
+/// can never leave the soft zone.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleFramingSettings {
+    /// The units the zone sizes are measured in
+    pub units: OgleFramingUnits,
+    /// The size of the dead zone
+    pub dead_zone: Vec2,
+    /// The size of the soft zone, which is never smaller than the dead zone
+    pub soft_zone: Vec2,
+    /// The time in seconds for the camera to catch up while in the soft zone
+    pub damping: f32,
+}
+
+impl Default for OgleFramingSettings {
+    fn default() -> Self {
+        Self {
+            units: OgleFramingUnits::ViewportFraction,
+            dead_zone: Vec2::ZERO,
+            soft_zone: Vec2::ZERO,
+            damping: 0.25,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub enum OgleFramingUnits {
+    /// Sizes are a fraction of the viewport, e.g. `0.5` is half of the viewport.
+    #[default]
+    ViewportFraction,
+    /// Sizes are in world units.
+    World,
+}
+
+/// Exponential smoothing of the camera rig.
+///
+/// Higher values are smoother, and `0.0` disables smoothing. Changes apply to live cameras.
+/// In [`OgleMode::Pancam`], position and zoom follow input directly, and only rotation is
+/// smoothed.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleSmoothingSettings {
+    /// Smoothness of the camera position along each axis
+    pub position: Vec2,
+    /// Smoothness of the camera zoom
+    pub zoom: f32,
+    /// Smoothness of the camera rotation
+    pub rotation: f32,
+    /// Whether the camera predicts ahead of its target, rather than lagging behind it.
+    pub predictive: bool,
+}
+
+impl Default for OgleSmoothingSettings {
+    fn default() -> Self {
+        Self {
+            position: Vec2::splat(1.5),
+            rotation: 1.5,
+            zoom: 1.5,
+            predictive: false,
+        }
+    }
+}
+
+/// Trauma-based screen shake.
+///
+/// The shake is applied after the camera changes are committed, so it never affects the rig.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleShakeSettings {
+    /// Trauma removed per second
+    pub decay: f32,
+    /// Maximum translational offset, in logical pixels
+    pub max_offset: Vec2,
+    /// Maximum rotational offset, in radians
+    pub max_roll: f32,
+    /// Frequency of the shake noise
+    pub frequency: f32,
+}
+
+impl Default for OgleShakeSettings {
+    fn default() -> Self {
+        Self {
+            decay: 1.0,
+            max_offset: Vec2::splat(30.0),
+            max_roll: 0.1,
+            frequency: 15.0,
+        }
+    }
+}
+
+/// Offsets the camera ahead of a followed entity, in the direction it is moving.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleLookAheadSettings {
+    /// Whether the camera looks ahead of followed entities.
+    pub enabled: bool,
+    /// Seconds of horizontal velocity to look ahead by
+    pub horizontal_gain: f32,
+    /// Seconds of vertical velocity to look ahead by
+    pub vertical_gain: f32,
+    /// The maximum look-ahead distance, in world units
+    pub max_distance: f32,
+    /// The time in seconds to ease into a new look-ahead offset
+    pub smoothing: f32,
+}
+
+impl Default for OgleLookAheadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            horizontal_gain: 0.5,
+            vertical_gain: 0.25,
+            max_distance: 200.0,
+            smoothing: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OglePancamSettings {
     /// Speed for keyboard movement
     pub keyboard_speed: f32,
@@ -133,6 +738,18 @@ pub struct OglePancamSettings {
     pub left_keys: Vec<KeyCode>,
     /// Keyboard keys for panning right
     pub right_keys: Vec<KeyCode>,
+    /// Keyboard keys for rotating counterclockwise, none by default
+    pub rotate_left_keys: Vec<KeyCode>,
+    /// Keyboard keys for rotating clockwise, none by default
+    pub rotate_right_keys: Vec<KeyCode>,
+    /// Speed for keyboard rotation, in radians per second
+    pub rotation_speed: f32,
+    /// Inertial panning after a drag is released
+    pub inertia: OgleInertiaSettings,
+    /// Gamepad bindings
+    pub gamepad: OgleGamepadSettings,
+    /// Panning when the cursor nears the edges of the viewport
+    pub edge_scroll: OgleEdgeScrollSettings,
 }
 
 impl Default for OglePancamSettings {
@@ -150,14 +767,160 @@ impl Default for OglePancamSettings {
             down_keys: DOWN_KEYS.to_vec(),
             left_keys: LEFT_KEYS.to_vec(),
             right_keys: RIGHT_KEYS.to_vec(),
+            rotate_left_keys: Vec::new(),
+            rotate_right_keys: Vec::new(),
+            rotation_speed: 1.5,
+            inertia: Default::default(),
+            gamepad: Default::default(),
+            edge_scroll: Default::default(),
+        }
+    }
+}
+
+/// Gamepad bindings for camera control.
+///
+/// Every connected gamepad controls the camera. Disabled by default, so games that use gamepads
+/// for gameplay don't also move the camera.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleGamepadSettings {
+    /// Whether gamepads can control the camera.
+    pub enabled: bool,
+    /// Stick axis for panning horizontally in pancam mode
+    pub pan_x_axis: GamepadAxis,
+    /// Stick axis for panning vertically in pancam mode
+    pub pan_y_axis: GamepadAxis,
+    /// Speed for stick panning at full tilt
+    pub pan_speed: f32,
+    /// Stick deflection below which input is ignored, between `0.0` and `1.0`
+    pub deadzone: f32,
+    /// Exponent of the stick response past the deadzone, where `1.0` is linear
+    pub response_curve: f32,
+    /// Gamepad buttons for zooming in
+    pub zoom_in_buttons: Vec<GamepadButton>,
+    /// Gamepad buttons for zooming out
+    pub zoom_out_buttons: Vec<GamepadButton>,
+    /// Speed for zooming at full press
+    pub zoom_speed: f32,
+    /// Stick axes for nudging the follow offset in normal mode, if any
+    pub nudge_axes: Option<(GamepadAxis, GamepadAxis)>,
+    /// Speed for nudging the follow offset at full tilt
+    pub nudge_speed: f32,
+}
+
+impl Default for OgleGamepadSettings {
+    fn default() -> Self {
+        const ZOOM_IN_BUTTONS: [GamepadButton; 2] =
+            [GamepadButton::RightTrigger2, GamepadButton::RightTrigger];
+        const ZOOM_OUT_BUTTONS: [GamepadButton; 2] =
+            [GamepadButton::LeftTrigger2, GamepadButton::LeftTrigger];
+        Self {
+            enabled: false,
+            pan_x_axis: GamepadAxis::LeftStickX,
+            pan_y_axis: GamepadAxis::LeftStickY,
+            pan_speed: 1000.0,
+            deadzone: 0.15,
+            response_curve: 1.5,
+            zoom_in_buttons: ZOOM_IN_BUTTONS.to_vec(),
+            zoom_out_buttons: ZOOM_OUT_BUTTONS.to_vec(),
+            zoom_speed: 2.0,
+            nudge_axes: Some((GamepadAxis::RightStickX, GamepadAxis::RightStickY)),
+            nudge_speed: 500.0,
+        }
+    }
+}
+
+/// RTS-style panning when the cursor nears the edges of the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleEdgeScrollSettings {
+    /// Whether the camera pans when the cursor nears the viewport edges.
+    pub enabled: bool,
+    /// Distance from the viewport edges where panning starts, in logical pixels
+    pub margin: f32,
+    /// Speed for edge panning with the cursor at the edge
+    pub speed: f32,
+    /// Exponent of the speed increase towards the edge, where `1.0` is linear
+    pub acceleration: f32,
+    /// Whether edge panning stops when the window is unfocused or the cursor leaves it.
+    pub pause_when_inactive: bool,
+}
+
+impl Default for OgleEdgeScrollSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            margin: 20.0,
+            speed: 1000.0,
+            acceleration: 1.0,
+            pause_when_inactive: true,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Kinetic panning, which keeps the camera moving after a pancam drag is released.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleInertiaSettings {
+    /// Whether the camera keeps moving after a drag is released.
+    pub enabled: bool,
+    /// How quickly the camera slows down, as a fraction of velocity lost per second
+    pub friction: f32,
+    /// The number of recent frames used to measure the drag velocity
+    pub samples: usize,
+    /// The speed in logical pixels per second below which the camera stops
+    pub min_speed: f32,
+    /// Whether the camera bounces off the bounds, rather than stopping at them.
+    pub bounce: bool,
+    /// The fraction of velocity kept when bouncing off the bounds
+    pub restitution: f32,
+}
+
+impl Default for OgleInertiaSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            friction: 5.0,
+            samples: 4,
+            min_speed: 5.0,
+            bounce: false,
+            restitution: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Reflect, MapEntities)]
+#[reflect(Debug, Clone, PartialEq, Default, MapEntities)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct OgleBoundingSettings {
     /// Whether the camera MUST remain bounded to the safe area.
     pub enabled: bool,
+    /// The shape of the safe area
+    pub shape: OgleBoundingShape,
+    /// An entity whose extent replaces `min_x`, `max_x`, `min_y` and `max_y` every frame.
+    ///
+    /// The extent is taken from the entity's [`OgleBoundsExtent`], or otherwise its `Aabb`,
+    /// which Bevy computes for sprites and meshes.
+    #[entities]
+    pub source: Option<Entity>,
+    /// How the scale is limited to fit the bounds in the viewport
+    pub fit: OgleFitPolicy,
+    /// Where to place the bounds when they are smaller than the viewport, from `-0.5` (left,
+    /// bottom) to `0.5` (right, top)
+    pub anchor: Vec2,
+    /// Overscrolling past the bounds, rather than hard clamping
+    pub elastic: OgleElasticSettings,
     /// The minimum scale for the camera
     pub min_scale: f32,
     /// The maximum scale for the camera
@@ -176,6 +939,11 @@ impl Default for OgleBoundingSettings {
     fn default() -> Self {
         Self {
             enabled: false,
+            shape: Default::default(),
+            source: None,
+            fit: Default::default(),
+            anchor: Vec2::ZERO,
+            elastic: Default::default(),
             min_scale: 0.00001,
             max_scale: f32::INFINITY,
             min_x: f32::NEG_INFINITY,
@@ -186,6 +954,97 @@ impl Default for OgleBoundingSettings {
     }
 }
 
+/// How the camera scale is limited by the bounds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub enum OgleFitPolicy {
+    /// The bounds always fill the viewport, and may be cropped.
+    #[default]
+    Fill,
+    /// The camera can zoom out until the entire bounds are shown, with letterboxing or
+    /// pillarboxing.
+    Fit,
+    /// The camera can zoom out until the width of the bounds is shown.
+    FitWidth,
+    /// The camera can zoom out until the height of the bounds is shown.
+    FitHeight,
+    /// The scale is never limited by the bounds, only the position is clamped.
+    None,
+}
+
+/// How cameras use [`OgleCameraZone`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleZoneSettings {
+    /// Whether the camera uses the zone containing its target.
+    pub enabled: bool,
+    /// The time in seconds to transition between the bounds of zones
+    pub transition: f32,
+}
+
+impl Default for OgleZoneSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            transition: 0.5,
+        }
+    }
+}
+
+/// Rubber-band bounds, which the camera can be dragged or pinched past with increasing
+/// resistance, and which it springs back inside once released.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct OgleElasticSettings {
+    /// Whether the camera can overscroll the bounds.
+    pub enabled: bool,
+    /// The furthest the camera can move past the bounds, in logical pixels
+    pub max_overscroll: f32,
+    /// The furthest the camera can zoom past the scale bounds, as a fraction of the scale
+    pub max_overzoom: f32,
+    /// The time in seconds for the camera to spring back inside the bounds
+    pub spring_back: f32,
+}
+
+impl Default for OgleElasticSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_overscroll: 100.0,
+            max_overzoom: 0.25,
+            spring_back: 0.15,
+        }
+    }
+}
+
+/// The shape of the area the camera is bounded to.
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub enum OgleBoundingShape {
+    /// The rectangle between `min_x`, `max_x`, `min_y` and `max_y`.
+    #[default]
+    Rect,
+    /// A union of rectangles.
+    ///
+    /// The viewport is kept inside one of the rectangles, so neighbouring rectangles must overlap
+    /// by at least a viewport for the camera to move between them.
+    Rects(Vec<Rect>),
+    /// A convex or concave polygon, given by its vertices in order.
+    ///
+    /// The scale is bounded to the extent of the polygon.
+    Polygon(Vec<Vec2>),
+}
+
 pub mod prelude {
-    pub use super::{OgleCam, OgleMode, OgleSettings, OgleTarget};
+    pub use super::{OgleCam, OgleGroup, OgleMode, OgleSettings, OgleTarget};
 }
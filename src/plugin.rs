@@ -1,41 +1,150 @@
-use crate::{systems, OgleSystems};
-use bevy::prelude::*;
+use crate::{
+    systems, OgleAnimationFinished, OgleArrived, OgleBoundingSettings, OgleBoundingShape,
+    OgleBoundsExtent, OgleCam, OgleCameraZone, OgleEdgeScrollSettings, OgleElasticSettings,
+    OgleFitPolicy, OgleFramingSettings, OgleFramingUnits, OgleGamepadSettings, OgleGroup,
+    OgleInertiaSettings, OgleInterpolated, OgleLookAheadSettings, OgleMode, OgleModeChanged,
+    OglePancamSettings, OgleRigSnapshot, OgleSettings, OgleShakeSettings, OgleSmoothingSettings,
+    OgleSystems, OgleTarget, OgleTargetLost, OgleTrauma, OgleZoneSettings, OgleZoomSettings,
+};
+use bevy::{
+    camera::CameraUpdateSystems,
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    prelude::*,
+    transform::TransformSystems,
+};
 
+/// Adds the camera systems, which run in `Update`.
+///
+/// Use [`OglePlugin::in_schedule`] to run them in another schedule.
 #[derive(Default)]
 pub struct OglePlugin;
 
+impl OglePlugin {
+    /// Run the camera systems in a schedule, such as `PostUpdate`.
+    ///
+    /// In `PostUpdate`, the cameras are committed before transforms are propagated and before
+    /// Bevy updates the camera projections.
+    pub fn in_schedule(schedule: impl ScheduleLabel) -> OgleScheduledPlugin {
+        OgleScheduledPlugin {
+            schedule: schedule.intern(),
+        }
+    }
+}
+
 impl Plugin for OglePlugin {
+    fn build(&self, app: &mut App) {
+        OglePlugin::in_schedule(Update).build(app);
+    }
+}
+
+/// Adds the camera systems to a schedule, created with [`OglePlugin::in_schedule`].
+pub struct OgleScheduledPlugin {
+    /// The schedule the camera systems run in
+    pub schedule: InternedScheduleLabel,
+}
+
+impl Plugin for OgleScheduledPlugin {
     fn build(&self, app: &mut App) {
         app.configure_sets(
-            Update,
+            self.schedule,
             (
                 OgleSystems::Update,
                 OgleSystems::Input,
                 OgleSystems::Correction,
                 OgleSystems::Commit,
+                OgleSystems::Effects,
             )
                 .chain(),
         );
+        if self.schedule == PostUpdate.intern() {
+            app.configure_sets(
+                PostUpdate,
+                OgleSystems::Effects
+                    .before(TransformSystems::Propagate)
+                    .before(CameraUpdateSystems),
+            );
+        }
+        app.register_type::<OgleCam>()
+            .register_type::<OgleRigSnapshot>()
+            .register_type::<OgleTarget>()
+            .register_type::<OgleGroup>()
+            .register_type::<OgleMode>()
+            .register_type::<OgleSettings>()
+            .register_type::<OgleZoomSettings>()
+            .register_type::<OgleFramingSettings>()
+            .register_type::<OgleFramingUnits>()
+            .register_type::<OgleLookAheadSettings>()
+            .register_type::<OgleSmoothingSettings>()
+            .register_type::<OgleShakeSettings>()
+            .register_type::<OgleBoundingSettings>()
+            .register_type::<OgleBoundingShape>()
+            .register_type::<OgleFitPolicy>()
+            .register_type::<OgleElasticSettings>()
+            .register_type::<OgleZoneSettings>()
+            .register_type::<OglePancamSettings>()
+            .register_type::<OgleInertiaSettings>()
+            .register_type::<OgleGamepadSettings>()
+            .register_type::<OgleEdgeScrollSettings>()
+            .register_type::<OgleCameraZone>()
+            .register_type::<OgleBoundsExtent>()
+            .register_type::<OgleInterpolated>();
+        app.add_message::<OgleTrauma>()
+            .add_message::<OgleAnimationFinished>()
+            .add_message::<OgleArrived>()
+            .add_message::<OgleModeChanged>()
+            .add_message::<OgleTargetLost>();
+        app.add_systems(FixedLast, systems::record_interpolated_transforms);
         app.add_systems(
-            Update,
-            systems::do_follow_target.in_set(OgleSystems::Update),
+            self.schedule,
+            (
+                systems::restore_rig_snapshots,
+                systems::update_bounds_from_entities,
+                systems::select_camera_zones,
+                systems::do_follow_target,
+                systems::do_camera_animation,
+            )
+                .chain()
+                .in_set(OgleSystems::Update),
         )
         .add_systems(
-            Update,
-            (systems::do_camera_zooming, systems::do_pancam_movement)
+            self.schedule,
+            (
+                systems::do_camera_zooming,
+                systems::do_pancam_movement,
+                systems::do_gamepad_nudge,
+                systems::do_touch_gestures,
+            )
                 .chain()
                 .in_set(OgleSystems::Input),
         )
         .add_systems(
-            Update,
+            self.schedule,
             systems::correct_to_camera_bounding.in_set(OgleSystems::Correction),
         )
         .add_systems(
-            Update,
-            systems::commit_camera_changes.in_set(OgleSystems::Commit),
+            self.schedule,
+            (systems::commit_camera_changes, systems::emit_camera_events)
+                .chain()
+                .in_set(OgleSystems::Commit),
+        )
+        .add_systems(
+            self.schedule,
+            systems::apply_camera_shake.in_set(OgleSystems::Effects),
         );
 
+        #[cfg(feature = "profile")]
+        app.init_asset::<crate::OgleProfile>()
+            .init_asset_loader::<crate::OgleProfileLoader>()
+            .add_systems(
+                self.schedule,
+                systems::apply_camera_profiles
+                    .before(systems::restore_rig_snapshots)
+                    .in_set(OgleSystems::Update),
+            );
+
         #[cfg(feature = "internal_bevy_egui")]
-        app.add_plugins(crate::egui_support::EguiPanCamPlugin);
+        app.add_plugins(crate::egui_support::EguiPanCamPlugin {
+            schedule: self.schedule,
+        });
     }
 }
@@ -0,0 +1,93 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+
+use crate::OgleSettings;
+
+/// Camera settings loaded from a RON (`.ogle.ron`) or JSON (`.ogle.json`) asset file.
+///
+/// The file holds an [`OgleSettings`], where any missing field keeps its default. Cameras with an
+/// [`OgleCamProfile`] use the profile's settings, and are updated whenever the asset changes,
+/// including when Bevy's `file_watcher` feature hot reloads it.
+///
+/// Entities can't be referenced from a file, so a loaded profile has no
+/// [`OgleBoundingSettings::source`](crate::OgleBoundingSettings::source), and applying a profile
+/// keeps the camera's own source.
+#[derive(Asset, TypePath, Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct OgleProfile {
+    pub settings: OgleSettings,
+}
+
+/// Makes a camera use the settings of an [`OgleProfile`] asset.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Deref, DerefMut)]
+pub struct OgleCamProfile(pub Handle<OgleProfile>);
+
+/// Loads [`OgleProfile`] assets from RON or JSON.
+#[derive(Default, TypePath)]
+pub struct OgleProfileLoader;
+
+/// An error loading an [`OgleProfile`].
+#[derive(Debug)]
+pub enum OgleProfileLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for OgleProfileLoaderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "could not read camera profile: {err}"),
+            Self::Ron(err) => write!(f, "could not parse RON camera profile: {err}"),
+            Self::Json(err) => write!(f, "could not parse JSON camera profile: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for OgleProfileLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Ron(err) => Some(err),
+            Self::Json(err) => Some(err),
+        }
+    }
+}
+
+impl AssetLoader for OgleProfileLoader {
+    type Asset = OgleProfile;
+    type Settings = ();
+    type Error = OgleProfileLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(OgleProfileLoaderError::Io)?;
+
+        let is_json = load_context
+            .path()
+            .path()
+            .extension()
+            .is_some_and(|extension| extension == "json");
+        let mut profile: OgleProfile = if is_json {
+            serde_json::from_slice(&bytes).map_err(OgleProfileLoaderError::Json)?
+        } else {
+            ron::de::from_bytes(&bytes).map_err(OgleProfileLoaderError::Ron)?
+        };
+        profile.settings.bounds.source = None;
+        Ok(profile)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ogle.ron", "ogle.json"]
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    f32::consts::{PI, TAU},
+    marker::PhantomData,
+};
+
+use bevy::math::Vec2;
+use dolly::{driver::RigDriver, prelude::Handedness, rig::RigUpdateParams, transform::Transform};
+
+/// Sets the rotation of the camera rig around the Z axis.
+#[derive(Debug)]
+pub(crate) struct OgleRotation {
+    /// The counterclockwise rotation in radians
+    pub angle: f32,
+}
+
+impl OgleRotation {
+    pub fn new(angle: f32) -> Self {
+        Self { angle }
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for OgleRotation {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        Transform {
+            position: params.parent.position,
+            rotation: angle_to_quat(self.angle),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The rotation around the Z axis of a quaternion, in radians.
+pub(crate) fn quat_to_angle(rotation: mint::Quaternion<f32>) -> f32 {
+    2.0 * rotation.v.z.atan2(rotation.s)
+}
+
+/// A quaternion rotating around the Z axis, by an angle in radians.
+pub(crate) fn angle_to_quat(angle: f32) -> mint::Quaternion<f32> {
+    let (sin, cos) = (angle * 0.5).sin_cos();
+    mint::Quaternion {
+        v: mint::Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: sin,
+        },
+        s: cos,
+    }
+}
+
+/// The shortest signed difference between two angles, in radians.
+pub(crate) fn angle_delta(from: f32, to: f32) -> f32 {
+    (to - from + PI).rem_euclid(TAU) - PI
+}
+
+/// The size of the axis-aligned box around a rectangle rotated by an angle in radians.
+pub(crate) fn rotated_size(size: Vec2, angle: f32) -> Vec2 {
+    let (sin, cos) = angle.sin_cos();
+    let (sin, cos) = (sin.abs(), cos.abs());
+    Vec2::new(cos * size.x + sin * size.y, sin * size.x + cos * size.y)
+}
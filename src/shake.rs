@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::OgleShakeSettings;
+
+/// Adds trauma to cameras, making them shake.
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct OgleTrauma {
+    /// The camera to shake, or every camera if `None`.
+    pub camera: Option<Entity>,
+    /// The amount of trauma to add, where `1.0` is the maximum.
+    pub amount: f32,
+}
+
+/// The trauma state of a camera.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Shake {
+    pub trauma: f32,
+    time: f32,
+}
+
+impl Shake {
+    /// Advance the shake, returning the translational offset in logical pixels and the roll in
+    /// radians.
+    pub fn update(&mut self, settings: &OgleShakeSettings, delta_secs: f32) -> (Vec2, f32) {
+        self.trauma = (self.trauma - settings.decay * delta_secs).max(0.0);
+        if self.trauma == 0.0 {
+            self.time = 0.0;
+            return (Vec2::ZERO, 0.0);
+        }
+        self.time += delta_secs;
+
+        // Squaring trauma gives a more pronounced falloff
+        let shake = self.trauma * self.trauma;
+        let t = self.time * settings.frequency;
+        let offset = Vec2::new(noise(0, t), noise(1, t)) * settings.max_offset * shake;
+        let roll = noise(2, t) * settings.max_roll * shake;
+        (offset, roll)
+    }
+}
+
+/// Smooth 1D value noise in `-1.0..=1.0`.
+fn noise(seed: u32, t: f32) -> f32 {
+    let cell = t.floor();
+    let fract = t - cell;
+    let a = hash(seed, cell as i32);
+    let b = hash(seed, cell as i32 + 1);
+    let smooth = fract * fract * (3.0 - 2.0 * fract);
+    a + (b - a) * smooth
+}
+
+/// Hash a lattice point to `-1.0..=1.0`.
+fn hash(seed: u32, n: i32) -> f32 {
+    let mut x = (n as u32).wrapping_mul(0x27d4_eb2d) ^ seed.wrapping_mul(0x1656_67b1);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x2c1b_3c6d);
+    x ^= x >> 12;
+    x = x.wrapping_mul(0x297a_2d39);
+    x ^= x >> 15;
+    x as f32 / u32::MAX as f32 * 2.0 - 1.0
+}
@@ -0,0 +1,89 @@
+use bevy::math::Vec3;
+use dolly::{driver::RigDriver, prelude::Handedness, rig::RigUpdateParams, transform::Transform};
+
+use crate::{
+    rotation::{angle_delta, angle_to_quat, quat_to_angle},
+    OgleSmoothingSettings,
+};
+
+/// An ad-hoc multiplier, matching dolly's `Smooth`, so smoothness values feel the same.
+const SMOOTHNESS_MULT: f32 = 8.0;
+
+/// Exponentially smooths the rig position and rotation, with a separate smoothness per axis and
+/// for zoom.
+#[derive(Debug)]
+pub(crate) struct OgleSmooth {
+    pub settings: OgleSmoothingSettings,
+    smoothed: Option<Vec3>,
+    smoothed_angle: Option<f32>,
+}
+
+impl OgleSmooth {
+    pub fn new(settings: OgleSmoothingSettings) -> Self {
+        Self {
+            settings,
+            smoothed: None,
+            smoothed_angle: None,
+        }
+    }
+
+    /// Forget the smoothing history, so the next update snaps to the parent.
+    pub fn reset(&mut self) {
+        self.smoothed = None;
+        self.smoothed_angle = None;
+    }
+
+    /// Continue smoothing from a previously committed position and rotation.
+    pub fn restore(&mut self, position: Vec3, angle: f32) {
+        self.smoothed = Some(position);
+        self.smoothed_angle = Some(angle);
+    }
+}
+
+impl<H: Handedness> RigDriver<H> for OgleSmooth {
+    fn update(&mut self, params: RigUpdateParams<H>) -> Transform<H> {
+        let parent = params.parent.position;
+        let target = Vec3::new(parent.x, parent.y, parent.z);
+
+        // Calculate the exponential blending of each axis based on frame time
+        let blend = |smoothness: f32| {
+            1.0 - (-SMOOTHNESS_MULT * params.delta_time_seconds / smoothness.max(1e-5)).exp()
+        };
+        let interp_t = Vec3::new(
+            blend(self.settings.position.x),
+            blend(self.settings.position.y),
+            blend(self.settings.zoom),
+        );
+
+        let prev = self.smoothed.unwrap_or(target);
+        let smoothed = prev + (target - prev) * interp_t;
+        self.smoothed = Some(smoothed);
+
+        // Rotation turns the shortest way towards the parent
+        let target_angle = quat_to_angle(params.parent.rotation);
+        let prev_angle = self.smoothed_angle.unwrap_or(target_angle);
+        let smoothed_angle =
+            prev_angle + angle_delta(prev_angle, target_angle) * blend(self.settings.rotation);
+        self.smoothed_angle = Some(smoothed_angle);
+
+        // Predictive smoothing mirrors the lag, so the output leads the parent instead
+        let (position, angle) = if self.settings.predictive {
+            (
+                target * 2.0 - smoothed,
+                target_angle + angle_delta(smoothed_angle, target_angle),
+            )
+        } else {
+            (smoothed, smoothed_angle)
+        };
+
+        Transform {
+            position: mint::Point3 {
+                x: position.x,
+                y: position.y,
+                z: position.z,
+            },
+            rotation: angle_to_quat(angle),
+            ..*params.parent
+        }
+    }
+}
@@ -1,119 +1,692 @@
 use std::ops::DerefMut;
 
-use crate::{OgleCam, OgleMode, OgleTarget};
+use crate::{
+    bounds::{bounded_center, bounded_position, bounded_scale_range, entity_extent},
+    interpolation::TargetTransforms,
+    smoothing::OgleSmooth,
+    viewport::CameraWindows,
+    OgleAnimationFinished, OgleArrived, OgleBoundingSettings, OgleBoundsExtent, OgleCam,
+    OgleCameraZone, OgleEdgeScrollSettings, OgleFramingSettings, OgleFramingUnits, OgleGroup,
+    OgleInterpolated, OgleMode, OgleModeChanged, OgleTarget, OgleTargetLost, OgleTrauma,
+};
 use bevy::{
+    camera::{primitives::Aabb, RenderTarget},
     input::mouse::{MouseScrollUnit, MouseWheel},
+    platform::collections::HashMap,
     prelude::*,
-    window::PrimaryWindow,
 };
 use dolly::prelude::*;
 
-pub fn do_follow_target(query_transform: Query<&Transform>, mut query_cam: Query<&mut OgleCam>) {
-    for mut cam in query_cam.iter_mut() {
-        if !matches!(cam.mode, OgleMode::Normal | OgleMode::MoveOnly) {
-            return;
+pub fn do_follow_target(
+    time: Res<Time>,
+    windows: CameraWindows,
+    query_transform: TargetTransforms,
+    mut query_cam: Query<(Entity, &mut OgleCam, &Camera, &RenderTarget)>,
+    mut target_lost_messages: MessageWriter<OgleTargetLost>,
+) {
+    for (entity, mut cam, camera, render_target) in query_cam.iter_mut() {
+        // Forget the target velocity while not following, so it doesn't span the gap
+        if !matches!(cam.mode, OgleMode::Normal | OgleMode::MoveOnly) || cam.is_animating() {
+            if cam.look_ahead.is_tracking() {
+                cam.look_ahead.reset();
+            }
+            continue;
         }
 
-        let prev_z = cam.rig.driver::<Position>().position.z;
+        let viewport_size = windows
+            .viewport_size(camera, render_target)
+            .unwrap_or_default();
+        let prev_pos = cam.rig.driver::<Position>().position;
 
-        match cam.target {
-            OgleTarget::Position(pos) => {
-                cam.rig.driver_mut::<Position>().position = mint::Point3 {
-                    x: pos.x,
-                    y: pos.y,
-                    z: prev_z,
+        // Report a missing target entity once, until it is found again
+        let lost_target = match cam.target {
+            OgleTarget::Entity(target) | OgleTarget::EntityWithOffset((target, _)) => Some(target),
+            _ => None,
+        }
+        .filter(|target| !query_transform.contains(*target));
+        if let Some(target) = lost_target.filter(|_| !cam.target_lost) {
+            target_lost_messages.write(OgleTargetLost {
+                camera: entity,
+                target,
+            });
+        }
+        if cam.target_lost != lost_target.is_some() {
+            cam.target_lost = lost_target.is_some();
+        }
+
+        // The point to follow, and the scale to frame it with
+        let followed = match cam.target {
+            OgleTarget::Position(pos) => Some((pos, prev_pos.z)),
+            OgleTarget::Entity(entity) => query_transform.get(entity).map(|transform| {
+                if cam.settings.follow_rotation {
+                    cam.set_rotation(transform.rotation.to_euler(EulerRot::ZYX).0);
                 }
+                (transform.translation.truncate(), prev_pos.z)
+            }),
+            OgleTarget::EntityWithOffset((entity, target_offset)) => {
+                query_transform.get(entity).map(|transform| {
+                    if cam.settings.follow_rotation {
+                        cam.set_rotation(transform.rotation.to_euler(EulerRot::ZYX).0);
+                    }
+                    (transform.translation.truncate() + target_offset, prev_pos.z)
+                })
             }
-            OgleTarget::Entity(entity) => {
-                if let Ok(transform) = query_transform.get(entity) {
-                    cam.rig.driver_mut::<Position>().position = mint::Point3 {
-                        x: transform.translation.x,
-                        y: transform.translation.y,
-                        z: prev_z,
+            OgleTarget::Group(ref group) => {
+                group_extents(group, &query_transform).map(|(center, half_extents)| {
+                    // Zoom so every member fits, but only when zooming is allowed
+                    let z = if cam.mode == OgleMode::Normal
+                        && viewport_size.x > 0.0
+                        && viewport_size.y > 0.0
+                    {
+                        let framed_size = (half_extents + group.padding) * 2.0;
+                        let scale_to_fit = framed_size / viewport_size;
+                        scale_to_fit
+                            .x
+                            .max(scale_to_fit.y)
+                            .clamp(cam.settings.bounds.min_scale, cam.settings.bounds.max_scale)
+                    } else {
+                        prev_pos.z
                     };
-                }
+                    (center, z)
+                })
             }
-            OgleTarget::EntityWithOffset((entity, offset)) => {
-                if let Ok(transform) = query_transform.get(entity) {
-                    cam.rig.driver_mut::<Position>().position = mint::Point3 {
-                        x: transform.translation.x + offset.x,
-                        y: transform.translation.y + offset.y,
-                        z: prev_z,
-                    };
-                }
+            OgleTarget::None => None,
+        };
+        let Some((target, z)) = followed else {
+            if cam.look_ahead.is_tracking() {
+                cam.look_ahead.reset();
             }
-            OgleTarget::None => {}
+            continue;
+        };
+        let z = match cam.zone.zoom {
+            Some(zoom) if cam.mode == OgleMode::Normal => zoom,
+            _ => z,
+        };
+
+        // Lead entity targets in the direction they are moving
+        let target = if matches!(cam.target, OgleTarget::Position(_)) {
+            cam.look_ahead.reset();
+            target
+        } else {
+            let cam = cam.as_mut();
+            target
+                + cam.look_ahead.update(
+                    &cam.settings.look_ahead,
+                    &cam.target,
+                    target,
+                    time.delta_secs(),
+                )
+        };
+        let target = target + cam.follow_offset;
+
+        // Keep the target inside the dead and soft zones
+        let framing = cam.zone.framing.unwrap_or(cam.settings.framing);
+        let center = frame_target(
+            &framing,
+            viewport_size * z,
+            Vec2::new(prev_pos.x, prev_pos.y),
+            target,
+            time.delta_secs(),
+        );
+        cam.rig.driver_mut::<Position>().position = mint::Point3 {
+            x: center.x,
+            y: center.y,
+            z,
+        };
+    }
+}
+
+#[cfg(feature = "profile")]
+pub fn apply_camera_profiles(
+    profiles: Res<Assets<crate::OgleProfile>>,
+    mut profile_events: MessageReader<AssetEvent<crate::OgleProfile>>,
+    mut query_cam: Query<(&mut OgleCam, Ref<crate::OgleCamProfile>)>,
+) {
+    // Profiles that finished loading or were hot reloaded
+    let changed_profiles = profile_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for (mut cam, profile) in query_cam.iter_mut() {
+        if !profile.is_changed() && !changed_profiles.contains(&profile.id()) {
+            continue;
+        }
+        if let Some(profile) = profiles.get(&profile.0) {
+            // Entity references belong to the running app, so the camera keeps its own
+            let source = cam.settings.bounds.source;
+            cam.settings = profile.settings.clone();
+            cam.settings.bounds.source = source;
         }
     }
 }
 
-pub fn do_camera_zooming(
+pub fn record_interpolated_transforms(mut query: Query<(&Transform, &mut OgleInterpolated)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.record(*transform);
+    }
+}
+
+pub fn restore_rig_snapshots(mut query_cam: Query<&mut OgleCam>) {
+    for mut cam in query_cam.iter_mut() {
+        // Cameras created by reflection, like from a scene, start with a default rig
+        if cam.rig_restored {
+            continue;
+        }
+        let snapshot = cam.rig_snapshot;
+        cam.restore(&snapshot);
+        cam.last_mode = cam.mode.clone();
+        cam.arrived = true;
+    }
+}
+
+pub fn update_bounds_from_entities(
+    query_extent: Query<(Option<&OgleBoundsExtent>, Option<&Aabb>, &GlobalTransform)>,
     mut query_cam: Query<&mut OgleCam>,
-    mut scroll_events: MessageReader<MouseWheel>,
 ) {
     for mut cam in query_cam.iter_mut() {
+        let Some(source) = cam.settings.bounds.source else {
+            continue;
+        };
+        let Some(rect) = query_extent
+            .get(source)
+            .ok()
+            .and_then(|(extent, aabb, transform)| entity_extent(extent, aabb, transform))
+        else {
+            continue;
+        };
+
+        let bounds = &mut cam.settings.bounds;
+        bounds.min_x = rect.min.x;
+        bounds.max_x = rect.max.x;
+        bounds.min_y = rect.min.y;
+        bounds.max_y = rect.max.y;
+    }
+}
+
+pub fn select_camera_zones(
+    query_transform: TargetTransforms,
+    query_zone: Query<(Entity, &OgleCameraZone)>,
+    mut query_cam: Query<&mut OgleCam>,
+) {
+    for mut cam in query_cam.iter_mut() {
+        let cam = cam.deref_mut();
+        if !cam.settings.zones.enabled {
+            if cam.zone.active.is_some() {
+                cam.zone.update(None, &cam.settings.bounds);
+            }
+            continue;
+        }
+
+        let focus = match cam.target {
+            OgleTarget::Position(pos) => Some(pos),
+            OgleTarget::Entity(entity) => query_transform
+                .get(entity)
+                .map(|transform| transform.translation.truncate()),
+            OgleTarget::EntityWithOffset((entity, offset)) => query_transform
+                .get(entity)
+                .map(|transform| transform.translation.truncate() + offset),
+            OgleTarget::Group(ref group) => {
+                group_extents(group, &query_transform).map(|(center, _)| center)
+            }
+            OgleTarget::None => None,
+        };
+
+        // The highest priority zone containing the target, preferring the current zone, or the
+        // current zone while the target is between zones
+        let active = focus
+            .and_then(|focus| {
+                query_zone
+                    .iter()
+                    .filter(|(_, zone)| zone.rect.contains(focus))
+                    .max_by_key(|(entity, zone)| (zone.priority, Some(*entity) == cam.zone.active))
+            })
+            .or_else(|| {
+                cam.zone
+                    .active
+                    .and_then(|entity| query_zone.get(entity).ok())
+            });
+        cam.zone.update(active, &cam.settings.bounds);
+    }
+}
+
+pub fn do_camera_animation(
+    time: Res<Time>,
+    mut finished_messages: MessageWriter<OgleAnimationFinished>,
+    mut query_cam: Query<(Entity, &mut OgleCam)>,
+) {
+    for (entity, mut cam) in query_cam.iter_mut() {
+        if cam.animation_interrupted {
+            cam.animation_interrupted = false;
+            finished_messages.write(OgleAnimationFinished {
+                camera: entity,
+                interrupted: true,
+            });
+        }
+
+        let Some(animation) = cam.animation.as_mut() else {
+            continue;
+        };
+        let position = animation.advance(time.delta_secs());
+        let finished = animation.is_finished();
+
+        // The easing replaces smoothing, so the rig lands exactly on the animated position
+        cam.rig.driver_mut::<Position>().position = mint::Point3 {
+            x: position.x,
+            y: position.y,
+            z: position.z,
+        };
+        cam.rig.driver_mut::<OgleSmooth>().reset();
+
+        if finished {
+            cam.animation = None;
+            finished_messages.write(OgleAnimationFinished {
+                camera: entity,
+                interrupted: false,
+            });
+        }
+    }
+}
+
+pub fn emit_camera_events(
+    mut query_cam: Query<(Entity, &mut OgleCam)>,
+    mut arrived_messages: MessageWriter<OgleArrived>,
+    mut mode_changed_messages: MessageWriter<OgleModeChanged>,
+) {
+    for (entity, mut cam) in query_cam.iter_mut() {
+        if cam.mode != cam.last_mode {
+            let current = cam.mode.clone();
+            let previous = std::mem::replace(&mut cam.last_mode, current.clone());
+            mode_changed_messages.write(OgleModeChanged {
+                camera: entity,
+                previous,
+                current,
+            });
+        }
+
+        // The camera is at rest once the committed rig reaches the driver position, to within
+        // half a pixel
+        let committed = cam.rig.final_transform.position;
+        let driver_pos = cam.rig.driver::<Position>().position;
+        let tolerance = 0.5 * driver_pos.z.abs();
+        let arrived = (committed.x - driver_pos.x).abs() <= tolerance
+            && (committed.y - driver_pos.y).abs() <= tolerance
+            && (committed.z - driver_pos.z).abs() <= driver_pos.z.abs() * 1e-3;
+        if arrived && !cam.arrived {
+            arrived_messages.write(OgleArrived {
+                camera: entity,
+                position: Vec3::new(committed.x, committed.y, committed.z),
+            });
+        }
+        if cam.arrived != arrived {
+            cam.arrived = arrived;
+        }
+    }
+}
+
+/// The camera center after framing `target` within the dead and soft zones.
+fn frame_target(
+    framing: &OgleFramingSettings,
+    world_viewport_size: Vec2,
+    center: Vec2,
+    target: Vec2,
+    delta_secs: f32,
+) -> Vec2 {
+    let (dead_zone, soft_zone) = match framing.units {
+        OgleFramingUnits::ViewportFraction => (
+            framing.dead_zone * world_viewport_size,
+            framing.soft_zone * world_viewport_size,
+        ),
+        OgleFramingUnits::World => (framing.dead_zone, framing.soft_zone),
+    };
+    let dead_half = dead_zone.max(Vec2::ZERO) * 0.5;
+    let soft_half = soft_zone.max(dead_zone).max(Vec2::ZERO) * 0.5;
+
+    // Fraction of the remaining distance to catch up on this frame
+    let catch_up = if framing.damping > 0.0 {
+        1.0 - (-delta_secs / framing.damping).exp()
+    } else {
+        1.0
+    };
+
+    let delta = target - center;
+    let excess = (delta.abs() - dead_half).max(Vec2::ZERO);
+    // Damp towards the dead zone, but never let the target leave the soft zone
+    let movement = (excess * catch_up).max(delta.abs() - soft_half);
+    center + delta.signum() * movement
+}
+
+/// The weighted centroid of a group, and the half extents of its members around it.
+fn group_extents(group: &OgleGroup, query_transform: &TargetTransforms) -> Option<(Vec2, Vec2)> {
+    let members = group
+        .members
+        .iter()
+        .filter_map(|(entity, weight)| {
+            query_transform
+                .get(*entity)
+                .map(|transform| (transform.translation.truncate(), weight.max(0.0)))
+        })
+        .collect::<Vec<_>>();
+    if members.is_empty() {
+        return None;
+    }
+
+    // Fall back to an unweighted centroid if no member carries any weight
+    let total_weight = members.iter().map(|(_, weight)| weight).sum::<f32>();
+    let center = if total_weight > 0.0 {
+        members
+            .iter()
+            .map(|(position, weight)| *position * *weight)
+            .sum::<Vec2>()
+            / total_weight
+    } else {
+        members.iter().map(|(position, _)| *position).sum::<Vec2>() / members.len() as f32
+    };
+
+    let half_extents = members
+        .iter()
+        .map(|(position, _)| (*position - center).abs())
+        .fold(Vec2::ZERO, Vec2::max);
+
+    Some((center, half_extents))
+}
+
+pub fn do_camera_zooming(
+    windows: CameraWindows,
+    keyboard_buttons: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut query_cam: Query<(Entity, &mut OgleCam, &Camera, &RenderTarget)>,
+    mut scroll_events: MessageReader<MouseWheel>,
+    mut pixel_scroll: Local<HashMap<Entity, f32>>,
+) {
+    let scroll_events = scroll_events.read().collect::<Vec<_>>();
+    let hovered = windows.hovered_camera(
+        query_cam
+            .iter()
+            .map(|(entity, _, camera, render_target)| (entity, camera, render_target)),
+    );
+
+    for (entity, mut cam, camera, render_target) in query_cam.iter_mut() {
         match cam.mode {
             OgleMode::Pancam | OgleMode::Normal | OgleMode::ZoomOnly => {}
-            OgleMode::Frozen | OgleMode::MoveOnly => return,
+            OgleMode::Frozen | OgleMode::MoveOnly => continue,
         };
+        if cam.is_animating() {
+            continue;
+        }
 
-        // Zoom handling
-        let scroll_amount = scroll_events
-            .read()
+        // Zoom handling, with the mouse wheel only zooming the camera under the cursor
+        let window = windows.window_entity(render_target);
+        let wheel_events = scroll_events
+            .iter()
+            .filter(|ev| hovered == Some(entity) && Some(ev.window) == window && ev.y != 0.);
+        let scroll_amount = wheel_events
+            .clone()
             .map(|ev| match ev.unit {
                 MouseScrollUnit::Pixel => ev.y,
                 MouseScrollUnit::Line => ev.y * cam.settings.zoom_sensitivity,
             })
             .sum::<f32>();
+        // Wheel steps between zoom levels, where positive steps zoom out. Every line is a step,
+        // while pixel scrolling like trackpads steps once per line's worth of pixels
+        let line_steps = wheel_events
+            .clone()
+            .filter(|ev| ev.unit == MouseScrollUnit::Line)
+            .map(|ev| ev.y.signum() as i32)
+            .sum::<i32>();
+        let pixels = wheel_events
+            .filter(|ev| ev.unit == MouseScrollUnit::Pixel)
+            .map(|ev| ev.y)
+            .sum::<f32>();
+        let pixel_steps = if pixels != 0.0 && !cam.settings.zoom.levels.is_empty() {
+            let remainder = pixel_scroll.entry(entity).or_default();
+            *remainder += pixels;
+            let steps = (*remainder / cam.settings.zoom_sensitivity.max(1.0)).trunc();
+            *remainder -= steps * cam.settings.zoom_sensitivity.max(1.0);
+            steps as i32
+        } else {
+            0
+        };
+        let wheel_steps = -(line_steps + pixel_steps);
+
+        // Keyboard zoom, where positive values zoom out
+        let zoom_settings = &cam.settings.zoom;
+        let (keyboard_zoom, keyboard_steps) = if keyboard_focused(hovered, entity) {
+            let held = |keys: &[KeyCode]| keys.iter().any(|key| keyboard_buttons.pressed(*key));
+            let pressed =
+                |keys: &[KeyCode]| keys.iter().any(|key| keyboard_buttons.just_pressed(*key));
+            (
+                held(&zoom_settings.zoom_out_keys) as i32 as f32
+                    - held(&zoom_settings.zoom_in_keys) as i32 as f32,
+                pressed(&zoom_settings.zoom_out_keys) as i32
+                    - pressed(&zoom_settings.zoom_in_keys) as i32,
+            )
+        } else {
+            (0.0, 0)
+        };
 
-        if scroll_amount != 0. {
-            cam.rig.driver_mut::<Position>().position.z *= 1.0 - scroll_amount * 0.001;
+        // Gamepad zoom, where positive values zoom out
+        let gamepad_settings = &cam.settings.pancam.gamepad;
+        let gamepad_zoom = if gamepad_settings.enabled {
+            gamepads
+                .iter()
+                .map(|gamepad| {
+                    gamepad_buttons_value(gamepad, &gamepad_settings.zoom_out_buttons)
+                        - gamepad_buttons_value(gamepad, &gamepad_settings.zoom_in_buttons)
+                })
+                .sum::<f32>()
+        } else {
+            0.0
+        };
+
+        if scroll_amount == 0. && keyboard_zoom == 0. && gamepad_zoom == 0. {
+            continue;
+        }
+
+        let Some(viewport_rect) = windows.viewport_rect(camera, render_target) else {
+            continue;
+        };
+        let scale = cam.rig.driver::<Position>().position.z;
+        let gamepad_factor = (gamepad_zoom * gamepad_settings.zoom_speed * time.delta_secs()).exp();
+        let new_scale = if zoom_settings.levels.is_empty() {
+            scale
+                * (1.0 - scroll_amount * 0.001)
+                * (keyboard_zoom * zoom_settings.keyboard_speed * time.delta_secs()).exp()
+                * gamepad_factor
+        } else {
+            step_zoom_level(&zoom_settings.levels, scale, wheel_steps + keyboard_steps)
+                * gamepad_factor
+        };
+        if new_scale == scale {
+            continue;
         }
+        let anchor = windows
+            .cursor_position(camera, render_target)
+            .filter(|_| scroll_amount != 0. && cam.settings.zoom_to_cursor);
+        zoom_camera(&mut cam, viewport_rect, new_scale, anchor);
     }
 }
 
+/// Whether keyboard input reaches a camera. Keyboard input goes to the camera under the cursor,
+/// or to every camera while the cursor is over none of them.
+fn keyboard_focused(hovered: Option<Entity>, entity: Entity) -> bool {
+    hovered.is_none_or(|hovered| hovered == entity)
+}
+
+/// Steps from a scale through the zoom levels, where positive steps zoom out.
+fn step_zoom_level(levels: &[f32], scale: f32, steps: i32) -> f32 {
+    // Relative tolerance, so the current level is never stepped to again
+    const TOLERANCE: f32 = 1e-3;
+    let mut scale = scale;
+    for _ in 0..steps.unsigned_abs() {
+        let next = if steps > 0 {
+            levels
+                .iter()
+                .copied()
+                .filter(|level| *level > scale * (1.0 + TOLERANCE))
+                .reduce(f32::min)
+        } else {
+            levels
+                .iter()
+                .copied()
+                .filter(|level| *level < scale * (1.0 - TOLERANCE))
+                .reduce(f32::max)
+        };
+        let Some(next) = next else {
+            break;
+        };
+        scale = next;
+    }
+    scale
+}
+
+/// Zoom a camera to a new scale, keeping the world position under `anchor` (in logical window
+/// pixels) in place, or the viewport center if there is no anchor.
+fn zoom_camera(cam: &mut OgleCam, viewport_rect: Rect, new_scale: f32, anchor: Option<Vec2>) {
+    let viewport_size = cam.bounding_viewport_size(viewport_rect.size());
+    let mut new_scale = new_scale;
+    let hard_bounds = cam.active_bounds().enabled && !cam.active_bounds().elastic.enabled;
+    if hard_bounds {
+        // Clamp ahead of the correction step, so the anchor is computed with the real scale
+        let scale_range = bounded_scale_range(cam.active_bounds(), viewport_size);
+        new_scale = new_scale.clamp(*scale_range.start(), *scale_range.end());
+    }
+    cam.rig.driver_mut::<Position>().position.z = new_scale;
+
+    let Some(anchor) = anchor else {
+        return;
+    };
+
+    // Offset of the anchor from the viewport center, in logical pixels with Y up, rotated to the
+    // world
+    let anchor_offset = anchor - viewport_rect.center();
+    let anchor_offset = Vec2::from_angle(cam.committed_rotation())
+        .rotate(Vec2::new(anchor_offset.x, -anchor_offset.y));
+
+    // The world position under the anchor, as it is currently shown on screen
+    let committed = cam.rig.final_transform.position;
+    let anchor_world = Vec2::new(committed.x, committed.y) + anchor_offset * committed.z;
+
+    // Place the camera so the anchor stays in place at the new scale
+    let mut new_center = anchor_world - anchor_offset * new_scale;
+    if hard_bounds {
+        new_center = bounded_center(cam.active_bounds(), viewport_size, new_scale, new_center);
+    }
+    let driver_pos = cam.rig.driver::<Position>().position;
+    let delta = new_center - Vec2::new(driver_pos.x, driver_pos.y);
+
+    // While following, the anchor shift must persist through the next follow update
+    if cam.mode == OgleMode::Normal && cam.target != OgleTarget::None {
+        cam.follow_offset += delta;
+    }
+    cam.rig.driver_mut::<Position>().position.x = new_center.x;
+    cam.rig.driver_mut::<Position>().position.y = new_center.y;
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn do_pancam_movement(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
+    windows: CameraWindows,
     mouse_buttons: Res<ButtonInput<MouseButton>>,
     keyboard_buttons: Res<ButtonInput<KeyCode>>,
-    mut query_cam: Query<(&mut OgleCam, &mut Transform, &Projection)>,
-    mut last_pos: Local<Option<Vec2>>,
+    gamepads: Query<&Gamepad>,
+    mut query_cam: Query<(Entity, &mut OgleCam, &Camera, &RenderTarget, &Projection)>,
+    mut last_pos: Local<HashMap<Entity, Vec2>>,
+    mut last_cursor: Local<HashMap<Entity, Vec2>>,
+    mut grabbed: Local<Option<Entity>>,
     time: Res<Time>,
 ) {
-    let Ok(window) = primary_window.single() else {
-        return;
-    };
+    let hovered = windows.hovered_camera(
+        query_cam
+            .iter()
+            .map(|(entity, _, camera, render_target, _)| (entity, camera, render_target)),
+    );
 
-    // Use position instead of MouseMotion, otherwise we don't get acceleration
-    // movement
-    let current_pos = match window.cursor_position() {
-        Some(c) => Vec2::new(c.x, -c.y),
-        None => return,
+    // A drag belongs to the camera it started on, until its grab buttons are released
+    let grab_pressed = |cam: &OgleCam| {
+        cam.settings
+            .pancam
+            .grab_buttons
+            .iter()
+            .any(|btn| mouse_buttons.pressed(*btn))
     };
-    let delta_device_pixels = current_pos - last_pos.unwrap_or(current_pos);
+    if grabbed.is_some_and(|entity| {
+        !query_cam
+            .get(entity)
+            .is_ok_and(|(_, cam, ..)| grab_pressed(cam))
+    }) {
+        *grabbed = None;
+    }
+    if grabbed.is_none() {
+        *grabbed = hovered.filter(|entity| {
+            query_cam.get(*entity).is_ok_and(|(_, cam, ..)| {
+                cam.settings
+                    .pancam
+                    .grab_buttons
+                    .iter()
+                    .any(|btn| mouse_buttons.just_pressed(*btn))
+            })
+        });
+    }
 
-    for (mut ogle_cam, transform, projection) in query_cam.iter_mut() {
-        if ogle_cam.mode != OgleMode::Pancam {
+    for (entity, mut ogle_cam, camera, render_target, projection) in query_cam.iter_mut() {
+        if ogle_cam.mode != OgleMode::Pancam || ogle_cam.is_animating() {
+            if ogle_cam.inertia.is_moving() {
+                ogle_cam.inertia.stop();
+            }
             continue;
         }
         let Projection::Orthographic(projection) = projection else {
             continue;
         };
 
-        let mouse_delta = if !ogle_cam
-            .settings
-            .pancam
-            .grab_buttons
-            .iter()
-            .any(|btn| mouse_buttons.pressed(*btn) && !mouse_buttons.just_pressed(*btn))
+        // Use position instead of MouseMotion, otherwise we don't get acceleration
+        // movement
+        let delta_device_pixels = windows
+            .window_entity(render_target)
+            .and_then(|window| {
+                let current_pos = windows.window(render_target)?.cursor_position()?;
+                let current_pos = Vec2::new(current_pos.x, -current_pos.y);
+                Some(current_pos - last_pos.get(&window).copied().unwrap_or(current_pos))
+            })
+            .unwrap_or(Vec2::ZERO);
+
+        let dragging = *grabbed == Some(entity);
+        let mouse_delta = if !dragging
+            || !ogle_cam
+                .settings
+                .pancam
+                .grab_buttons
+                .iter()
+                .any(|btn| mouse_buttons.pressed(*btn) && !mouse_buttons.just_pressed(*btn))
         {
             Vec2::ZERO
         } else {
             delta_device_pixels * projection.scale
         };
 
+        // Inertia delta, measured from the drag and applied once it is released
+        let inertia_settings = ogle_cam.settings.pancam.inertia;
+        if dragging {
+            ogle_cam.elastic.held = true;
+            ogle_cam
+                .inertia
+                .drag(&inertia_settings, -mouse_delta, time.delta_secs());
+        } else {
+            ogle_cam.inertia.release(&inertia_settings);
+        }
+        let inertia_delta =
+            ogle_cam
+                .inertia
+                .advance(&inertia_settings, projection.scale, time.delta_secs());
+
         // Keyboard delta
-        let keyboard_direction = {
+        let keyboard_direction = if !keyboard_focused(hovered, entity) {
+            Vec2::ZERO
+        } else {
             let mut direction = Vec2::ZERO;
             if ogle_cam
                 .settings
@@ -158,31 +731,291 @@ pub fn do_pancam_movement(
             * ogle_cam.settings.pancam.keyboard_speed
             * projection.scale;
 
-        // Get final delta
-        let delta = mouse_delta - keyboard_delta;
+        // Gamepad delta
+        let gamepad_settings = &ogle_cam.settings.pancam.gamepad;
+        let gamepad_delta = if gamepad_settings.enabled {
+            time.delta_secs()
+                * gamepad_stick(
+                    &gamepads,
+                    gamepad_settings.pan_x_axis,
+                    gamepad_settings.pan_y_axis,
+                    gamepad_settings.deadzone,
+                    gamepad_settings.response_curve,
+                )
+                * gamepad_settings.pan_speed
+                * projection.scale
+        } else {
+            Vec2::ZERO
+        };
+
+        // Edge scroll delta, for the camera under the cursor
+        let edge_settings = &ogle_cam.settings.pancam.edge_scroll;
+        let edge_direction = if edge_settings.enabled && !dragging {
+            let window = windows.window(render_target);
+            let window_active = window.is_some_and(|window| window.focused)
+                && window.is_some_and(|window| window.cursor_position().is_some());
+            let cursor_position = match window.and_then(Window::cursor_position) {
+                Some(_) if hovered != Some(entity) => None,
+                Some(cursor_position) => Some(cursor_position),
+                // Keep scrolling towards where the cursor left the window
+                None => windows
+                    .window_entity(render_target)
+                    .and_then(|window| last_cursor.get(&window).copied()),
+            };
+            match (
+                cursor_position,
+                windows.viewport_rect(camera, render_target),
+            ) {
+                (Some(cursor_position), Some(viewport_rect))
+                    if viewport_rect.contains(cursor_position)
+                        && (window_active || !edge_settings.pause_when_inactive) =>
+                {
+                    edge_scroll_direction(edge_settings, viewport_rect, cursor_position)
+                }
+                _ => Vec2::ZERO,
+            }
+        } else {
+            Vec2::ZERO
+        };
+        let edge_delta =
+            time.delta_secs() * edge_direction * edge_settings.speed * projection.scale;
+
+        // Keyboard rotation
+        if keyboard_focused(hovered, entity) {
+            let pancam = &ogle_cam.settings.pancam;
+            let held = |keys: &[KeyCode]| keys.iter().any(|key| keyboard_buttons.pressed(*key));
+            let direction = held(&pancam.rotate_left_keys) as i32 as f32
+                - held(&pancam.rotate_right_keys) as i32 as f32;
+            if direction != 0. {
+                let angle =
+                    ogle_cam.rotation() + direction * pancam.rotation_speed * time.delta_secs();
+                ogle_cam.set_rotation(angle);
+            }
+        }
+
+        // Get final delta, rotated from the screen to the world
+        let delta = mouse_delta - keyboard_delta - gamepad_delta - edge_delta - inertia_delta;
         if delta == Vec2::ZERO {
             continue;
         }
+        let delta = Vec2::from_angle(ogle_cam.committed_rotation()).rotate(delta);
+
+        // The proposed new camera position. Pancam commits the rig position directly, so this
+        // matches the camera transform without any post-commit effects, like shake.
+        let driver_pos = ogle_cam.rig.driver::<Position>().position;
+        let mut new_pos = Vec2::new(driver_pos.x, driver_pos.y) - delta;
 
-        // The proposed new camera position
-        let new_pos = transform.translation.truncate() - delta;
+        // Inertia stops or bounces at the bounds
+        if ogle_cam.active_bounds().enabled && ogle_cam.inertia.is_moving() {
+            if let Some(viewport_size) = windows.viewport_size(camera, render_target) {
+                let bounded_pos = bounded_center(
+                    ogle_cam.active_bounds(),
+                    ogle_cam.bounding_viewport_size(viewport_size),
+                    driver_pos.z,
+                    new_pos,
+                );
+                ogle_cam
+                    .inertia
+                    .hit_bounds(&inertia_settings, bounded_pos.cmpne(new_pos));
+                new_pos = bounded_pos;
+            }
+        }
         ogle_cam.rig.driver_mut::<Position>().position.x = new_pos.x;
         ogle_cam.rig.driver_mut::<Position>().position.y = new_pos.y;
     }
-    *last_pos = Some(current_pos);
+
+    for (window, cursor_position) in windows.cursor_positions() {
+        match cursor_position {
+            Some(c) => {
+                last_pos.insert(window, Vec2::new(c.x, -c.y));
+                last_cursor.insert(window, c);
+            }
+            None => {
+                last_pos.remove(&window);
+            }
+        }
+    }
 }
 
-pub fn correct_to_camera_bounding(
-    primary_window: Query<&Window, With<PrimaryWindow>>,
-    mut query_cam: Query<(&mut OgleCam, &Camera, &Projection)>,
+/// The edge scroll direction for a cursor near the edges of a viewport, in world orientation.
+fn edge_scroll_direction(
+    settings: &OgleEdgeScrollSettings,
+    viewport_rect: Rect,
+    cursor_position: Vec2,
+) -> Vec2 {
+    // Scroll faster the deeper the cursor is into the margin
+    let margin = settings.margin.max(f32::EPSILON);
+    let strength = |distance: f32| {
+        ((margin - distance) / margin)
+            .clamp(0.0, 1.0)
+            .powf(settings.acceleration)
+    };
+    Vec2::new(
+        strength(viewport_rect.max.x - cursor_position.x)
+            - strength(cursor_position.x - viewport_rect.min.x),
+        strength(cursor_position.y - viewport_rect.min.y)
+            - strength(viewport_rect.max.y - cursor_position.y),
+    )
+}
+
+pub fn do_gamepad_nudge(
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut query_cam: Query<&mut OgleCam>,
+) {
+    for mut cam in query_cam.iter_mut() {
+        if cam.mode != OgleMode::Normal || cam.is_animating() {
+            continue;
+        }
+        let gamepad_settings = &cam.settings.pancam.gamepad;
+        let Some((x_axis, y_axis)) = gamepad_settings
+            .nudge_axes
+            .filter(|_| gamepad_settings.enabled)
+        else {
+            continue;
+        };
+
+        let stick = gamepad_stick(
+            &gamepads,
+            x_axis,
+            y_axis,
+            gamepad_settings.deadzone,
+            gamepad_settings.response_curve,
+        );
+        if stick == Vec2::ZERO {
+            continue;
+        }
+        let nudge = time.delta_secs()
+            * stick
+            * gamepad_settings.nudge_speed
+            * cam.rig.driver::<Position>().position.z;
+        cam.follow_offset += nudge;
+    }
+}
+
+/// The combined deflection of a stick across all gamepads, after the deadzone and response curve.
+fn gamepad_stick(
+    gamepads: &Query<&Gamepad>,
+    x_axis: GamepadAxis,
+    y_axis: GamepadAxis,
+    deadzone: f32,
+    response_curve: f32,
+) -> Vec2 {
+    let stick = gamepads
+        .iter()
+        .map(|gamepad| {
+            Vec2::new(
+                gamepad.get(x_axis).unwrap_or(0.0),
+                gamepad.get(y_axis).unwrap_or(0.0),
+            )
+        })
+        .sum::<Vec2>()
+        .clamp_length_max(1.0);
+
+    // Rescale the live zone past the deadzone to the full range, then apply the curve
+    let magnitude = stick.length();
+    if magnitude <= deadzone {
+        return Vec2::ZERO;
+    }
+    let response = ((magnitude - deadzone) / (1.0 - deadzone).max(f32::EPSILON))
+        .clamp(0.0, 1.0)
+        .powf(response_curve);
+    stick / magnitude * response
+}
+
+/// The strongest press of any of the buttons on a gamepad, between `0.0` and `1.0`.
+fn gamepad_buttons_value(gamepad: &Gamepad, buttons: &[GamepadButton]) -> f32 {
+    buttons
+        .iter()
+        .map(|button| {
+            gamepad
+                .get(*button)
+                .unwrap_or(if gamepad.pressed(*button) { 1.0 } else { 0.0 })
+        })
+        .fold(0.0, f32::max)
+}
+
+pub fn do_touch_gestures(
+    windows: CameraWindows,
+    touches: Res<Touches>,
+    mut query_cam: Query<(Entity, &mut OgleCam, &Camera, &RenderTarget, &Projection)>,
 ) {
-    let Ok(window) = primary_window.single() else {
+    // The gesture midpoint this frame and last frame, and the pinch distances if pinching
+    let active_touches = touches.iter().collect::<Vec<_>>();
+    let (position, previous_position, pinch) = match active_touches.as_slice() {
+        [] => return,
+        [touch] => (touch.position(), touch.previous_position(), None),
+        [first, second, ..] => (
+            (first.position() + second.position()) * 0.5,
+            (first.previous_position() + second.previous_position()) * 0.5,
+            Some((
+                first.position().distance(second.position()),
+                first
+                    .previous_position()
+                    .distance(second.previous_position()),
+            )),
+        ),
+    };
+
+    // Only the camera under the gesture responds
+    let Some(touched) = windows.camera_at(
+        query_cam
+            .iter()
+            .map(|(entity, _, camera, render_target, _)| (entity, camera, render_target)),
+        previous_position,
+    ) else {
+        return;
+    };
+    let Ok((_, mut cam, camera, render_target, projection)) = query_cam.get_mut(touched) else {
+        return;
+    };
+    if cam.is_animating() {
+        return;
+    }
+    let Projection::Orthographic(projection) = projection else {
+        return;
+    };
+    let Some(viewport_rect) = windows.viewport_rect(camera, render_target) else {
         return;
     };
-    let window_size = window.size();
+    cam.elastic.held = true;
+
+    // Pinch to zoom around the pinch midpoint
+    if let Some((distance, previous_distance)) = pinch {
+        let can_zoom = matches!(
+            cam.mode,
+            OgleMode::Pancam | OgleMode::Normal | OgleMode::ZoomOnly
+        );
+        if can_zoom && distance > 0.0 && previous_distance > 0.0 && distance != previous_distance {
+            let new_scale = cam.rig.driver::<Position>().position.z * previous_distance / distance;
+            zoom_camera(&mut cam, viewport_rect, new_scale, Some(position));
+        }
+    }
 
-    for (mut cam, camera, projection) in query_cam.iter_mut() {
-        if !cam.settings.bounds.enabled {
+    // Drag with one or two fingers to pan
+    if cam.mode == OgleMode::Pancam {
+        let delta = position - previous_position;
+        if delta != Vec2::ZERO {
+            let delta =
+                Vec2::from_angle(cam.committed_rotation()).rotate(Vec2::new(delta.x, -delta.y));
+            let driver_pos = cam.rig.driver::<Position>().position;
+            let new_pos = Vec2::new(driver_pos.x, driver_pos.y) - delta * projection.scale;
+            cam.rig.driver_mut::<Position>().position.x = new_pos.x;
+            cam.rig.driver_mut::<Position>().position.y = new_pos.y;
+        }
+    }
+}
+
+pub fn correct_to_camera_bounding(
+    time: Res<Time>,
+    windows: CameraWindows,
+    mut query_cam: Query<(&mut OgleCam, &Camera, &RenderTarget, &Projection)>,
+) {
+    for (mut cam, camera, render_target, projection) in query_cam.iter_mut() {
+        let cam = cam.deref_mut();
+        let bounds = cam.zone.bounds.as_ref().unwrap_or(&cam.settings.bounds);
+        if !bounds.enabled && cam.zone.transition.is_none() {
+            cam.elastic.held = false;
             continue;
         }
 
@@ -190,80 +1023,47 @@ pub fn correct_to_camera_bounding(
             continue;
         };
 
-        // Calculate the bounds area size
-        let bounds_width = cam.settings.bounds.max_x - cam.settings.bounds.min_x;
-        let bounds_height = cam.settings.bounds.max_y - cam.settings.bounds.min_y;
-
-        // Get viewport size in pixels
-        let viewport_size = camera.logical_viewport_size().unwrap_or(window_size);
-
-        // Calculate what scale would be needed to fit the bounds area exactly in the viewport
-        let scale_to_fit_width = bounds_width / viewport_size.x;
-        let scale_to_fit_height = bounds_height / viewport_size.y;
-        // Use min instead of max to fill the viewport (may crop bounds)
-        let scale_to_fit_bounds = scale_to_fit_width.min(scale_to_fit_height);
-
-        // The maximum allowed scale (most zoomed out) should not exceed what's needed to fit bounds
-        // But we also respect the user's min/max scale settings
-        let max_allowed_scale = cam.settings.bounds.max_scale.min(scale_to_fit_bounds);
-
-        // Ensure we have a valid range - if scale_to_fit_bounds is smaller than min_scale,
-        // we prioritize the bounds constraint over the user's min_scale
-        let effective_min_scale = cam.settings.bounds.min_scale.min(scale_to_fit_bounds);
-        let scale_range = effective_min_scale..=max_allowed_scale;
-
-        // Bound the zoom
-        cam.rig.driver_mut::<Position>().position.z = cam
-            .rig
-            .driver::<Position>()
-            .position
-            .z
-            .clamp(*scale_range.start(), *scale_range.end());
-
-        // Get the current scale after clamping
-        let current_scale = cam.rig.driver::<Position>().position.z;
-
-        // Calculate viewport size in world units
-        let world_viewport_width = viewport_size.x * current_scale;
-        let world_viewport_height = viewport_size.y * current_scale;
-
-        // Calculate half sizes for easier math
-        let half_width = world_viewport_width * 0.5;
-        let half_height = world_viewport_height * 0.5;
-
-        // Calculate the bounds for the camera center position
-        // The camera center must stay within these bounds to keep the entire viewport within the target area
-        let effective_min_x = cam.settings.bounds.min_x + half_width;
-        let effective_max_x = cam.settings.bounds.max_x - half_width;
-        let effective_min_y = cam.settings.bounds.min_y + half_height;
-        let effective_max_y = cam.settings.bounds.max_y - half_height;
-
-        // Only apply bounds if they make sense (i.e., the bounded area is larger than the viewport)
-        if effective_min_x <= effective_max_x {
-            cam.rig.driver_mut::<Position>().position.x = cam
-                .rig
-                .driver::<Position>()
-                .position
-                .x
-                .clamp(effective_min_x, effective_max_x);
-        } else {
-            // If bounds are too tight, center the camera
-            let bounds_center_x = (cam.settings.bounds.min_x + cam.settings.bounds.max_x) * 0.5;
-            cam.rig.driver_mut::<Position>().position.x = bounds_center_x;
+        // Get viewport size in pixels, as the box around the rotated viewport
+        let Some(viewport_size) = windows.viewport_size(camera, render_target) else {
+            continue;
+        };
+        let viewport_size = cam.bounding_viewport_size(viewport_size);
+
+        let position = cam.rig.driver::<Position>().position;
+        let position = Vec3::new(position.x, position.y, position.z);
+        let bound = |bounds: &OgleBoundingSettings| {
+            if bounds.enabled {
+                bounded_position(bounds, viewport_size, position)
+            } else {
+                position
+            }
+        };
+        let mut bounded = bound(bounds);
+
+        // Ease from the bounds of the previous zone
+        if let Some((from, progress)) = cam.zone.transition.as_mut() {
+            *progress = if cam.settings.zones.transition > 0.0 {
+                (*progress + time.delta_secs() / cam.settings.zones.transition).min(1.0)
+            } else {
+                1.0
+            };
+            bounded = bound(from).lerp(bounded, EaseFunction::SmoothStep.sample_clamped(*progress));
+            if *progress >= 1.0 {
+                cam.zone.transition = None;
+            }
         }
 
-        if effective_min_y <= effective_max_y {
-            cam.rig.driver_mut::<Position>().position.y = cam
-                .rig
-                .driver::<Position>()
-                .position
-                .y
-                .clamp(effective_min_y, effective_max_y);
-        } else {
-            // If bounds are too tight, center the camera
-            let bounds_center_y = (cam.settings.bounds.min_y + cam.settings.bounds.max_y) * 0.5;
-            cam.rig.driver_mut::<Position>().position.y = bounds_center_y;
+        // Elastic bounds let the camera overshoot, and spring back
+        if bounds.elastic.enabled {
+            bounded = cam
+                .elastic
+                .correct(&bounds.elastic, position, bounded, time.delta_secs());
         }
+        cam.rig.driver_mut::<Position>().position = mint::Point3 {
+            x: bounded.x,
+            y: bounded.y,
+            z: bounded.z,
+        };
     }
 }
 
@@ -275,18 +1075,57 @@ pub fn commit_camera_changes(
         let Projection::Orthographic(ref mut projection) = projection.deref_mut() else {
             continue;
         };
-        // Apply final transform update
+        // Apply final transform update, with the latest smoothing settings
+        let smoothing = cam.settings.smoothing;
+        cam.rig.driver_mut::<OgleSmooth>().settings = smoothing;
         cam.rig.update(time.delta_secs());
         if cam.mode == OgleMode::Pancam {
+            // Pancam commits the position and zoom directly, so drags and zooms anchored to the
+            // cursor keep the world under the cursor in place
             let driver_pos = cam.rig.driver::<Position>().position;
-            cam.rig.final_transform.position.x = driver_pos.x;
-            cam.rig.final_transform.position.y = driver_pos.y;
+            cam.rig.final_transform.position = driver_pos;
+            let committed_rotation = cam.committed_rotation();
+            cam.rig.driver_mut::<OgleSmooth>().restore(
+                Vec3::new(driver_pos.x, driver_pos.y, driver_pos.z),
+                committed_rotation,
+            );
         }
         camera_transform.translation = Vec3::new(
             cam.rig.final_transform.position.x,
             cam.rig.final_transform.position.y,
             camera_transform.translation.z,
         );
+        camera_transform.rotation = Quat::from_rotation_z(cam.committed_rotation());
         projection.scale = cam.rig.final_transform.position.z;
+        cam.rig_snapshot = cam.snapshot();
+    }
+}
+
+pub fn apply_camera_shake(
+    time: Res<Time>,
+    mut trauma_messages: MessageReader<OgleTrauma>,
+    mut query_cam: Query<(Entity, &mut OgleCam, &Projection, &mut Transform)>,
+) {
+    let trauma_messages = trauma_messages.read().copied().collect::<Vec<_>>();
+
+    for (entity, mut cam, projection, mut camera_transform) in query_cam.iter_mut() {
+        let Projection::Orthographic(projection) = projection else {
+            continue;
+        };
+
+        for trauma in trauma_messages
+            .iter()
+            .filter(|trauma| trauma.camera.is_none_or(|camera| camera == entity))
+        {
+            cam.add_trauma(trauma.amount);
+        }
+
+        let settings = cam.settings.shake;
+        let (offset, roll) = cam.shake.update(&settings, time.delta_secs());
+
+        // The transform is rewritten on every commit, so the shake is applied on top of it
+        let offset = Vec2::from_angle(cam.committed_rotation()).rotate(offset);
+        camera_transform.translation += (offset * projection.scale).extend(0.0);
+        camera_transform.rotation *= Quat::from_rotation_z(roll);
     }
 }
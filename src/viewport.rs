@@ -0,0 +1,181 @@
+use bevy::{
+    camera::RenderTarget,
+    ecs::system::SystemParam,
+    prelude::*,
+    window::{PrimaryWindow, WindowRef},
+};
+
+use crate::OgleCam;
+
+/// Converts between window, viewport and world coordinates for an [`OgleCam`].
+///
+/// Conversions use the committed state of the camera rig, so they agree with what is shown on
+/// screen, excluding effects like shake. Window and viewport positions are in logical pixels
+/// from the top left corner.
+///
+/// This reads every [`OgleCam`], so a system can't also take a `Query<&mut OgleCam>`, which
+/// panics with conflicting access. To move a camera from such a system, use
+/// [`OgleCam::viewport_to_world`], [`OgleCam::world_to_viewport`] or [`OgleCam::visible_rect`]
+/// with the viewport size from [`Camera::logical_viewport_size`].
+#[derive(SystemParam)]
+pub struct OgleViewports<'w, 's> {
+    windows: CameraWindows<'w, 's>,
+    cameras: Query<'w, 's, (&'static OgleCam, &'static Camera, &'static RenderTarget)>,
+}
+
+impl OgleViewports<'_, '_> {
+    /// Convert a viewport position of a camera to a world position.
+    pub fn viewport_to_world(&self, camera: Entity, viewport_position: Vec2) -> Option<Vec2> {
+        let (cam, camera, render_target) = self.cameras.get(camera).ok()?;
+        let viewport_size = self.windows.viewport_size(camera, render_target)?;
+        Some(cam.viewport_to_world(viewport_size, viewport_position))
+    }
+
+    /// Convert a world position to a viewport position of a camera.
+    pub fn world_to_viewport(&self, camera: Entity, world_position: Vec2) -> Option<Vec2> {
+        let (cam, camera, render_target) = self.cameras.get(camera).ok()?;
+        let viewport_size = self.windows.viewport_size(camera, render_target)?;
+        Some(cam.world_to_viewport(viewport_size, world_position))
+    }
+
+    /// Convert a position in the window a camera renders to, to a world position.
+    pub fn window_to_world(&self, camera: Entity, window_position: Vec2) -> Option<Vec2> {
+        let viewport_min = self.viewport_rect(camera)?.min;
+        self.viewport_to_world(camera, window_position - viewport_min)
+    }
+
+    /// Convert a world position to a position in the window a camera renders to.
+    pub fn world_to_window(&self, camera: Entity, world_position: Vec2) -> Option<Vec2> {
+        let viewport_min = self.viewport_rect(camera)?.min;
+        Some(self.world_to_viewport(camera, world_position)? + viewport_min)
+    }
+
+    /// Get the world position under the cursor, if the cursor is over the camera's viewport.
+    pub fn cursor_to_world(&self, camera: Entity) -> Option<Vec2> {
+        let (_, camera_component, render_target) = self.cameras.get(camera).ok()?;
+        let cursor_position = self
+            .windows
+            .cursor_position(camera_component, render_target)?;
+        self.window_to_world(camera, cursor_position)
+    }
+
+    /// Get the logical rect of a camera's viewport in the window it renders to.
+    pub fn viewport_rect(&self, camera: Entity) -> Option<Rect> {
+        let (_, camera, render_target) = self.cameras.get(camera).ok()?;
+        self.windows.viewport_rect(camera, render_target)
+    }
+
+    /// Get the world-space rect visible to a camera.
+    pub fn visible_rect(&self, camera: Entity) -> Option<Rect> {
+        let (cam, camera, render_target) = self.cameras.get(camera).ok()?;
+        let viewport_size = self.windows.viewport_size(camera, render_target)?;
+        Some(cam.visible_rect(viewport_size))
+    }
+
+    /// Whether a world position is visible to a camera.
+    pub fn is_point_visible(&self, camera: Entity, world_position: Vec2) -> bool {
+        let Ok((cam, camera, render_target)) = self.cameras.get(camera) else {
+            return false;
+        };
+        self.windows
+            .viewport_size(camera, render_target)
+            .is_some_and(|viewport_size| {
+                Rect::from_corners(Vec2::ZERO, viewport_size)
+                    .contains(cam.world_to_viewport(viewport_size, world_position))
+            })
+    }
+
+    /// Whether any part of a world-space rect is visible to a camera.
+    ///
+    /// While the camera is rotated, this tests against the box around the rotated viewport.
+    pub fn is_rect_visible(&self, camera: Entity, rect: Rect) -> bool {
+        self.visible_rect(camera)
+            .is_some_and(|visible| !visible.intersect(rect).is_empty())
+    }
+}
+
+/// Resolves the windows that cameras render to.
+#[derive(SystemParam)]
+pub(crate) struct CameraWindows<'w, 's> {
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    windows: Query<'w, 's, (Entity, &'static Window)>,
+}
+
+impl CameraWindows<'_, '_> {
+    /// The window entity a camera renders to, if it renders to a window.
+    pub fn window_entity(&self, render_target: &RenderTarget) -> Option<Entity> {
+        match render_target {
+            RenderTarget::Window(WindowRef::Primary) => self.primary_window.single().ok(),
+            RenderTarget::Window(WindowRef::Entity(entity)) => Some(*entity),
+            _ => None,
+        }
+    }
+
+    /// The window a camera renders to, if it renders to a window.
+    pub fn window(&self, render_target: &RenderTarget) -> Option<&Window> {
+        let entity = self.window_entity(render_target)?;
+        self.windows.get(entity).ok().map(|(_, window)| window)
+    }
+
+    /// The logical size of a camera's viewport, falling back to the size of its window.
+    pub fn viewport_size(&self, camera: &Camera, render_target: &RenderTarget) -> Option<Vec2> {
+        camera
+            .logical_viewport_size()
+            .or_else(|| self.window(render_target).map(Window::size))
+    }
+
+    /// The logical rect of a camera's viewport in its window, falling back to the whole window.
+    pub fn viewport_rect(&self, camera: &Camera, render_target: &RenderTarget) -> Option<Rect> {
+        camera.logical_viewport_rect().or_else(|| {
+            self.window(render_target)
+                .map(|window| Rect::from_corners(Vec2::ZERO, window.size()))
+        })
+    }
+
+    /// The cursor position in a camera's window, if the cursor is over the camera's viewport.
+    pub fn cursor_position(&self, camera: &Camera, render_target: &RenderTarget) -> Option<Vec2> {
+        let cursor_position = self.window(render_target)?.cursor_position()?;
+        self.viewport_rect(camera, render_target)?
+            .contains(cursor_position)
+            .then_some(cursor_position)
+    }
+
+    /// The cursor position in every window.
+    pub fn cursor_positions(&self) -> impl Iterator<Item = (Entity, Option<Vec2>)> + '_ {
+        self.windows
+            .iter()
+            .map(|(entity, window)| (entity, window.cursor_position()))
+    }
+
+    /// The topmost active camera whose viewport is under the cursor.
+    pub fn hovered_camera<'a>(
+        &self,
+        cameras: impl IntoIterator<Item = (Entity, &'a Camera, &'a RenderTarget)>,
+    ) -> Option<Entity> {
+        cameras
+            .into_iter()
+            .filter(|(_, camera, render_target)| {
+                camera.is_active && self.cursor_position(camera, render_target).is_some()
+            })
+            .max_by_key(|(_, camera, _)| camera.order)
+            .map(|(entity, _, _)| entity)
+    }
+
+    /// The topmost active camera whose viewport contains a logical window position.
+    pub fn camera_at<'a>(
+        &self,
+        cameras: impl IntoIterator<Item = (Entity, &'a Camera, &'a RenderTarget)>,
+        position: Vec2,
+    ) -> Option<Entity> {
+        cameras
+            .into_iter()
+            .filter(|(_, camera, render_target)| {
+                camera.is_active
+                    && self
+                        .viewport_rect(camera, render_target)
+                        .is_some_and(|rect| rect.contains(position))
+            })
+            .max_by_key(|(_, camera, _)| camera.order)
+            .map(|(entity, _, _)| entity)
+    }
+}
@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+#[cfg(feature = "serde")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{OgleBoundingSettings, OgleBoundingShape, OgleFramingSettings};
+
+/// An area of the world with its own camera bounds, such as a room.
+///
+/// While a camera's target is inside the zone, the zone's bounds, zoom and framing are used
+/// instead of the camera settings. The zone stays active while the target is between zones.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct OgleCameraZone {
+    /// The area of the zone, in world units
+    pub rect: Rect,
+    /// The bounds while in the zone, or the zone's area if `None`
+    pub bounds: Option<OgleBoundingShape>,
+    /// The camera scale while in the zone, when following in `OgleMode::Normal`
+    pub zoom: Option<f32>,
+    /// The follow framing while in the zone
+    pub framing: Option<OgleFramingSettings>,
+    /// Where zones overlap, the zone with the highest priority is used
+    pub priority: i32,
+}
+
+impl OgleCameraZone {
+    /// Create a zone bounded to its area.
+    pub fn new(rect: Rect) -> Self {
+        Self {
+            rect,
+            bounds: None,
+            zoom: None,
+            framing: None,
+            priority: 0,
+        }
+    }
+
+    /// Set the bounds while in the zone.
+    pub fn with_bounds(mut self, bounds: OgleBoundingShape) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// Set the camera scale while in the zone.
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = Some(zoom);
+        self
+    }
+
+    /// Set the follow framing while in the zone.
+    pub fn with_framing(mut self, framing: OgleFramingSettings) -> Self {
+        self.framing = Some(framing);
+        self
+    }
+
+    /// Set the priority over overlapping zones.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// The active camera zone of a camera, and the transition from the previous bounds.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ZoneState {
+    pub active: Option<Entity>,
+    /// The bounds of the active zone
+    pub bounds: Option<OgleBoundingSettings>,
+    pub zoom: Option<f32>,
+    pub framing: Option<OgleFramingSettings>,
+    /// The bounds being transitioned from, and the progress of the transition
+    pub transition: Option<(OgleBoundingSettings, f32)>,
+}
+
+impl ZoneState {
+    /// Switch to a zone, or to the camera settings if `None`.
+    pub fn update(
+        &mut self,
+        active: Option<(Entity, &OgleCameraZone)>,
+        base_bounds: &OgleBoundingSettings,
+    ) {
+        let entity = active.map(|(entity, _)| entity);
+        if entity != self.active {
+            let from = self.bounds.take().unwrap_or_else(|| base_bounds.clone());
+            self.transition = Some((from, 0.0));
+            self.active = entity;
+        }
+        self.bounds = active.map(|(_, zone)| OgleBoundingSettings {
+            enabled: true,
+            shape: zone
+                .bounds
+                .clone()
+                .unwrap_or_else(|| OgleBoundingShape::Rects(vec![zone.rect])),
+            ..base_bounds.clone()
+        });
+        self.zoom = active.and_then(|(_, zone)| zone.zoom);
+        self.framing = active.and_then(|(_, zone)| zone.framing);
+    }
+}
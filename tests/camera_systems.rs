@@ -0,0 +1,707 @@
+mod common;
+
+use bevy::{
+    camera::CameraUpdateSystems,
+    ecs::entity::{EntityHashMap, MapEntities},
+    prelude::*,
+};
+use bevy_ogle::{
+    prelude::*, OgleBoundingSettings, OgleInterpolated, OgleLookAheadSettings, OglePlugin,
+    OgleZoomSettings,
+};
+use common::{assert_near, instant_settings, TestApp, WINDOW_SIZE};
+
+const CENTER: Vec2 = Vec2::new(WINDOW_SIZE.x / 2.0, WINDOW_SIZE.y / 2.0);
+
+fn bounded_settings() -> OgleSettings {
+    OgleSettings {
+        bounds: OgleBoundingSettings {
+            enabled: true,
+            min_x: -1000.0,
+            max_x: 1000.0,
+            min_y: -1000.0,
+            max_y: 1000.0,
+            ..default()
+        },
+        ..instant_settings()
+    }
+}
+
+#[test]
+fn follow_entity_target() {
+    let mut test = TestApp::new();
+    let target = test.spawn_target(Vec2::new(100.0, 50.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(100.0, 50.0),
+        0.01,
+    );
+
+    test.move_entity(target, Vec2::new(-200.0, 300.0));
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(-200.0, 300.0),
+        0.01,
+    );
+}
+
+#[test]
+fn follow_is_smoothed() {
+    let mut test = TestApp::new();
+    let target = test.spawn_target(Vec2::new(500.0, 0.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        OgleSettings::default(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+
+    test.update();
+    let first = test.camera_translation(camera);
+    assert!(first.x > 0.0 && first.x < 500.0, "moved to {first}");
+
+    test.update_secs(5.0);
+    assert_near(test.camera_translation(camera), Vec2::new(500.0, 0.0), 1.0);
+}
+
+#[test]
+fn follow_with_offset() {
+    let mut test = TestApp::new();
+    let target = test.spawn_target(Vec2::new(100.0, 100.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::EntityWithOffset((target, Vec2::new(0.0, 50.0))),
+        OgleMode::Normal,
+    ));
+
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(100.0, 150.0),
+        0.01,
+    );
+}
+
+#[test]
+fn look_ahead_ignores_target_jumps() {
+    let mut test = TestApp::new();
+    let first = test.spawn_target(Vec2::ZERO);
+    let second = test.spawn_target(Vec2::new(1000.0, 0.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        OgleSettings {
+            look_ahead: OgleLookAheadSettings {
+                enabled: true,
+                smoothing: 0.0,
+                ..default()
+            },
+            ..instant_settings()
+        },
+        OgleTarget::Entity(first),
+        OgleMode::Normal,
+    ));
+    test.update_frames(5);
+    assert_near(test.camera_translation(camera), Vec2::ZERO, 0.01);
+
+    // Switching targets doesn't count as the target moving
+    test.cam_mut(camera).target = OgleTarget::Entity(second);
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(1000.0, 0.0),
+        0.01,
+    );
+
+    // Neither does movement while the camera isn't following
+    test.cam_mut(camera).mode = OgleMode::Frozen;
+    test.update();
+    test.move_entity(second, Vec2::new(-1000.0, 0.0));
+    test.update();
+    test.cam_mut(camera).mode = OgleMode::Normal;
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(-1000.0, 0.0),
+        0.01,
+    );
+}
+
+#[test]
+fn follow_every_camera() {
+    // A camera without a target must not stop the cameras after it from following
+    let mut test = TestApp::new();
+    let target = test.spawn_target(Vec2::new(100.0, 50.0));
+    let idle = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Normal,
+    ));
+    let lost = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(Entity::PLACEHOLDER),
+        OgleMode::Normal,
+    ));
+    let following = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+
+    test.update();
+    assert_near(test.camera_translation(idle), Vec2::ZERO, 0.01);
+    assert_near(test.camera_translation(lost), Vec2::ZERO, 0.01);
+    assert_near(
+        test.camera_translation(following),
+        Vec2::new(100.0, 50.0),
+        0.01,
+    );
+}
+
+#[test]
+fn frozen_camera_does_not_follow() {
+    let mut test = TestApp::new();
+    let target = test.spawn_target(Vec2::new(100.0, 50.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Frozen,
+    ));
+
+    test.update_frames(10);
+    assert_near(test.camera_translation(camera), Vec2::ZERO, 0.01);
+}
+
+#[test]
+fn follow_in_post_update() {
+    let mut test = TestApp::with_plugin(OglePlugin::in_schedule(PostUpdate));
+    let target = test.spawn_target(Vec2::new(100.0, 50.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(100.0, 50.0),
+        0.01,
+    );
+}
+
+#[test]
+fn commit_before_camera_update_in_post_update() {
+    /// The camera translation and scale seen by Bevy's camera update systems
+    #[derive(Resource, Default)]
+    struct SeenByCameraUpdate(Option<(Vec2, f32)>);
+
+    fn record(
+        query: Query<(&Transform, &Projection), With<OgleCam>>,
+        mut seen: ResMut<SeenByCameraUpdate>,
+    ) {
+        for (transform, projection) in &query {
+            if let Projection::Orthographic(projection) = projection {
+                seen.0 = Some((transform.translation.truncate(), projection.scale));
+            }
+        }
+    }
+
+    let mut test = TestApp::with_plugin(OglePlugin::in_schedule(PostUpdate));
+    test.app
+        .init_resource::<SeenByCameraUpdate>()
+        .add_systems(PostUpdate, record.in_set(CameraUpdateSystems));
+    let target = test.spawn_target(Vec2::new(100.0, 50.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.scroll(-1.0);
+    test.update();
+    let scale = test.camera_scale(camera);
+    assert!(scale > 1.0, "zoomed to {scale}");
+    let (translation, seen_scale) = test
+        .app
+        .world()
+        .resource::<SeenByCameraUpdate>()
+        .0
+        .expect("camera update systems did not run");
+    assert_near(translation, Vec2::new(100.0, 50.0), 0.01);
+    assert_eq!(seen_scale, scale);
+}
+
+#[test]
+fn follow_interpolated_target() {
+    let mut test = TestApp::new();
+    test.app.add_systems(
+        FixedUpdate,
+        |mut query: Query<&mut Transform, With<OgleInterpolated>>| {
+            for mut transform in query.iter_mut() {
+                transform.translation.x += 10.0;
+            }
+        },
+    );
+    let target = test.spawn_target(Vec2::ZERO);
+    test.app
+        .world_mut()
+        .entity_mut(target)
+        .insert(OgleInterpolated::default());
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+
+    // The camera follows between the last two fixed timesteps, behind the target
+    for _ in 0..30 {
+        test.update();
+        let world = test.app.world();
+        let overstep = world.resource::<Time<Fixed>>().overstep_fraction();
+        let interpolated = world
+            .get::<OgleInterpolated>(target)
+            .and_then(|interpolated| interpolated.interpolate(overstep))
+            .map_or(Vec2::ZERO, |transform| transform.translation.truncate());
+        let translation = test.camera_translation(camera);
+        assert_near(translation, interpolated, 0.01);
+        let target_x = world.get::<Transform>(target).unwrap().translation.x;
+        assert!(
+            translation.x <= target_x + 0.01,
+            "{translation} ahead of {target_x}"
+        );
+    }
+    assert!(test.camera_translation(camera).x > 0.0);
+}
+
+#[test]
+fn scroll_zooms_hovered_camera() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::ZoomOnly,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.scroll(1.0);
+    test.update();
+    let zoomed_in = test.camera_scale(camera);
+    assert!(zoomed_in < 1.0, "zoomed to {zoomed_in}");
+
+    test.scroll(-2.0);
+    test.update();
+    let zoomed_out = test.camera_scale(camera);
+    assert!(zoomed_out > 1.0, "zoomed to {zoomed_out}");
+}
+
+#[test]
+fn scroll_outside_window_does_not_zoom() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::ZoomOnly,
+    ));
+    test.set_cursor(None);
+
+    test.scroll(1.0);
+    test.update();
+    assert_eq!(test.camera_scale(camera), 1.0);
+}
+
+#[test]
+fn keyboard_zoom() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::ZoomOnly,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.press_key(KeyCode::Equal);
+    test.update_secs(0.5);
+    test.release_key(KeyCode::Equal);
+    test.update();
+    let scale = test.camera_scale(camera);
+    assert!(scale < 1.0, "zoomed to {scale}");
+
+    // Zooming stops when the key is released
+    test.update_frames(10);
+    assert_eq!(test.camera_scale(camera), scale);
+}
+
+#[test]
+fn zoom_to_cursor_in_pancam_keeps_anchor() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        OgleSettings {
+            zoom_to_cursor: true,
+            ..default()
+        },
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+    let cursor = CENTER + Vec2::new(300.0, -200.0);
+    test.set_cursor(Some(cursor));
+    test.update();
+
+    // The world position under the cursor, where window y points down
+    let world_under_cursor = |test: &TestApp| {
+        let offset = (cursor - CENTER) * Vec2::new(1.0, -1.0);
+        test.camera_translation(camera) + offset * test.camera_scale(camera)
+    };
+    let anchor = world_under_cursor(&test);
+
+    // With default smoothing, the anchor holds on every frame of the zoom
+    for _ in 0..3 {
+        test.scroll(1.0);
+        for _ in 0..10 {
+            test.update();
+            assert_near(world_under_cursor(&test), anchor, 0.01);
+        }
+    }
+    assert!(test.camera_scale(camera) < 1.0);
+}
+
+#[test]
+fn zoom_levels() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        OgleSettings {
+            zoom: OgleZoomSettings {
+                levels: vec![0.5, 1.0, 2.0],
+                ..default()
+            },
+            ..instant_settings()
+        },
+        OgleTarget::None,
+        OgleMode::ZoomOnly,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.scroll(-1.0);
+    test.update();
+    assert_eq!(test.camera_scale(camera), 2.0);
+
+    // Stepping past the last level keeps it
+    test.scroll(-1.0);
+    test.update();
+    assert_eq!(test.camera_scale(camera), 2.0);
+
+    test.press_key(KeyCode::Equal);
+    test.update();
+    test.release_key(KeyCode::Equal);
+    test.press_key(KeyCode::Equal);
+    test.update();
+    assert_eq!(test.camera_scale(camera), 0.5);
+}
+
+#[test]
+fn zoom_levels_with_pixel_scrolling() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        OgleSettings {
+            zoom: OgleZoomSettings {
+                levels: vec![0.25, 0.5, 1.0, 2.0, 4.0],
+                ..default()
+            },
+            ..instant_settings()
+        },
+        OgleTarget::None,
+        OgleMode::ZoomOnly,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    // Less than a line's worth of pixels doesn't step
+    for _ in 0..25 {
+        test.scroll_pixels(-2.0);
+    }
+    test.update();
+    assert_eq!(test.camera_scale(camera), 1.0);
+
+    // Many small events in a frame step once per line's worth of pixels
+    for _ in 0..30 {
+        test.scroll_pixels(-2.0);
+    }
+    test.update();
+    assert_eq!(test.camera_scale(camera), 2.0);
+}
+
+#[test]
+fn frozen_camera_does_not_zoom() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Frozen,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.scroll(1.0);
+    test.update();
+    assert_eq!(test.camera_scale(camera), 1.0);
+}
+
+#[test]
+fn keyboard_pan() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.press_key(KeyCode::ArrowRight);
+    test.update_secs(1.0);
+    test.release_key(KeyCode::ArrowRight);
+    test.update();
+    let position = test.camera_translation(camera);
+    assert_near(position, Vec2::new(1000.0, 0.0), 1.0);
+
+    test.press_key(KeyCode::ArrowUp);
+    test.update_secs(0.5);
+    assert!(test.camera_translation(camera).y > 400.0);
+}
+
+#[test]
+fn keyboard_pan_without_cursor() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+    test.set_cursor(None);
+
+    test.press_key(KeyCode::ArrowRight);
+    test.update_secs(0.5);
+    assert!(test.camera_translation(camera).x > 400.0);
+}
+
+#[test]
+fn keyboard_pan_goes_to_hovered_camera() {
+    let mut test = TestApp::new();
+    let below = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+    let above = test
+        .app
+        .world_mut()
+        .spawn((
+            OgleCam::new(instant_settings(), OgleTarget::None, OgleMode::Pancam),
+            Camera {
+                order: 1,
+                ..default()
+            },
+        ))
+        .id();
+    test.set_cursor(Some(CENTER));
+
+    test.press_key(KeyCode::ArrowRight);
+    test.update_secs(0.5);
+    assert!(test.camera_translation(above).x > 400.0);
+    assert_near(test.camera_translation(below), Vec2::ZERO, 0.01);
+}
+
+#[test]
+fn keyboard_rotation_is_opt_in() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+
+    test.press_key(KeyCode::KeyQ);
+    test.update_secs(0.5);
+    assert_eq!(test.cam(camera).rotation(), 0.0);
+
+    test.cam_mut(camera).settings.pancam.rotate_left_keys = vec![KeyCode::KeyQ];
+    test.update_secs(0.5);
+    assert!(test.cam(camera).rotation() > 0.0);
+}
+
+#[test]
+fn mouse_drag_pan() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+    test.set_cursor(Some(CENTER));
+    test.update();
+
+    test.press_mouse(MouseButton::Left);
+    test.update();
+    test.set_cursor(Some(CENTER + Vec2::new(100.0, 50.0)));
+    test.update();
+    test.release_mouse(MouseButton::Left);
+    test.update();
+
+    // The world moves with the cursor, where window y points down
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(-100.0, 50.0),
+        0.01,
+    );
+}
+
+#[test]
+fn pan_only_in_pancam_mode() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Normal,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.press_key(KeyCode::ArrowRight);
+    test.update_secs(0.5);
+    assert_near(test.camera_translation(camera), Vec2::ZERO, 0.01);
+}
+
+#[test]
+fn bounds_clamp_pan() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        bounded_settings(),
+        OgleTarget::None,
+        OgleMode::Pancam,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    test.press_key(KeyCode::ArrowRight);
+    test.press_key(KeyCode::ArrowUp);
+    test.update_secs(3.0);
+
+    // The viewport edge stops at the bounds
+    let max_center = Vec2::splat(1000.0) - WINDOW_SIZE / 2.0;
+    assert_near(test.camera_translation(camera), max_center, 0.01);
+}
+
+#[test]
+fn bounds_clamp_follow() {
+    let mut test = TestApp::new();
+    let target = test.spawn_target(Vec2::new(-5000.0, 0.0));
+    let camera = test.spawn_camera(OgleCam::new(
+        bounded_settings(),
+        OgleTarget::Entity(target),
+        OgleMode::Normal,
+    ));
+
+    test.update();
+    let min_x = -1000.0 + WINDOW_SIZE.x / 2.0;
+    assert_near(test.camera_translation(camera), Vec2::new(min_x, 0.0), 0.01);
+}
+
+#[test]
+fn bounds_clamp_zoom() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        bounded_settings(),
+        OgleTarget::None,
+        OgleMode::ZoomOnly,
+    ));
+    test.set_cursor(Some(CENTER));
+
+    for _ in 0..20 {
+        test.scroll(-10.0);
+        test.update();
+    }
+
+    // The viewport never grows larger than the bounds
+    let max_scale = (2000.0 / WINDOW_SIZE).min_element();
+    let scale = test.camera_scale(camera);
+    assert!(scale <= max_scale + 0.001, "zoomed to {scale}");
+}
+
+#[test]
+fn teleport_skips_smoothing() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        OgleSettings::default(),
+        OgleTarget::None,
+        OgleMode::Frozen,
+    ));
+    test.update();
+
+    test.cam_mut(camera).teleport(Vec3::new(300.0, -200.0, 2.0));
+    test.update();
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(300.0, -200.0),
+        0.01,
+    );
+    assert_eq!(test.camera_scale(camera), 2.0);
+}
+
+#[test]
+fn teleport_cancels_animation() {
+    let mut test = TestApp::new();
+    let camera = test.spawn_camera(OgleCam::new(
+        instant_settings(),
+        OgleTarget::None,
+        OgleMode::Frozen,
+    ));
+    test.cam_mut(camera)
+        .animate_to(Vec2::new(1000.0, 0.0), 1.0, 2.0, EaseFunction::Linear);
+    test.update_frames(10);
+    assert!(test.cam(camera).is_animating());
+
+    test.cam_mut(camera).teleport(Vec3::new(-100.0, 0.0, 1.0));
+    assert!(!test.cam(camera).is_animating());
+    test.update_frames(10);
+    assert_near(
+        test.camera_translation(camera),
+        Vec2::new(-100.0, 0.0),
+        0.01,
+    );
+}
+
+#[test]
+fn map_camera_entities() {
+    let mut test = TestApp::new();
+    let player = test.spawn_target(Vec2::ZERO);
+    let level = test.spawn_target(Vec2::ZERO);
+    let mut settings = instant_settings();
+    settings.bounds.source = Some(level);
+    let camera = test.spawn_camera(OgleCam::new(
+        settings,
+        OgleTarget::Group(OgleGroup::new([player, level])),
+        OgleMode::Normal,
+    ));
+
+    let mapped_player = test.spawn_target(Vec2::ZERO);
+    let mapped_level = test.spawn_target(Vec2::ZERO);
+    let mut entity_map = EntityHashMap::default();
+    entity_map.insert(player, mapped_player);
+    entity_map.insert(level, mapped_level);
+    Component::map_entities(&mut *test.cam_mut(camera), &mut entity_map);
+
+    let cam = test.cam(camera);
+    assert_eq!(cam.settings.bounds.source, Some(mapped_level));
+    assert_eq!(
+        cam.target,
+        OgleTarget::Group(OgleGroup::new([mapped_player, mapped_level]))
+    );
+
+    let mut target = OgleTarget::EntityWithOffset((player, Vec2::ONE));
+    target.map_entities(&mut entity_map);
+    assert_eq!(
+        target,
+        OgleTarget::EntityWithOffset((mapped_player, Vec2::ONE))
+    );
+}
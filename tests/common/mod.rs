@@ -0,0 +1,238 @@
+//! A headless app for testing camera systems, with a fake window and injectable input and time.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::{
+    input::{
+        mouse::{MouseScrollUnit, MouseWheel},
+        touch::Touches,
+    },
+    prelude::*,
+    time::TimeUpdateStrategy,
+    window::{PrimaryWindow, WindowResolution},
+};
+use bevy_ogle::{prelude::*, OgleFramingSettings, OglePlugin, OgleSmoothingSettings};
+
+/// The size of the fake window, in logical pixels.
+pub const WINDOW_SIZE: Vec2 = Vec2::new(800.0, 600.0);
+
+/// The time advanced by every frame, in seconds.
+pub const FRAME_SECS: f32 = 1.0 / 60.0;
+
+/// Settings without smoothing or damping, so the camera reaches its goal in a single frame.
+pub fn instant_settings() -> OgleSettings {
+    OgleSettings {
+        smoothing: OgleSmoothingSettings {
+            position: Vec2::ZERO,
+            rotation: 0.0,
+            zoom: 0.0,
+            ..default()
+        },
+        framing: OgleFramingSettings {
+            damping: 0.0,
+            ..default()
+        },
+        ..default()
+    }
+}
+
+pub struct TestApp {
+    pub app: App,
+    pub window: Entity,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// Create an app with [`OglePlugin`] and a focused primary window of [`WINDOW_SIZE`], and run
+    /// its first frame.
+    pub fn new() -> Self {
+        Self::with_plugin(OglePlugin)
+    }
+
+    /// Create an app with a camera plugin, such as one from [`OglePlugin::in_schedule`].
+    ///
+    /// Input resources are inserted directly instead of using `InputPlugin`, so tests can press
+    /// buttons without sending window events.
+    pub fn with_plugin(plugin: impl Plugin) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                FRAME_SECS,
+            )))
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<Touches>()
+            .add_message::<MouseWheel>();
+        #[cfg(feature = "profile")]
+        app.add_plugins(bevy::asset::AssetPlugin {
+            file_path: "tests/assets".to_string(),
+            ..default()
+        });
+        app.add_plugins(plugin);
+
+        let window = app
+            .world_mut()
+            .spawn((
+                Window {
+                    resolution: WindowResolution::new(WINDOW_SIZE.x as u32, WINDOW_SIZE.y as u32),
+                    focused: true,
+                    ..default()
+                },
+                PrimaryWindow,
+            ))
+            .id();
+
+        // The first frame has no time delta, so settle it before any camera is spawned
+        app.update();
+
+        Self { app, window }
+    }
+
+    /// Spawn a camera rendering to the primary window.
+    pub fn spawn_camera(&mut self, cam: OgleCam) -> Entity {
+        self.app.world_mut().spawn(cam).id()
+    }
+
+    /// Spawn an entity at a position, to be followed.
+    pub fn spawn_target(&mut self, position: Vec2) -> Entity {
+        self.app
+            .world_mut()
+            .spawn(Transform::from_translation(position.extend(0.0)))
+            .id()
+    }
+
+    /// Move an entity to a position.
+    pub fn move_entity(&mut self, entity: Entity, position: Vec2) {
+        self.app
+            .world_mut()
+            .get_mut::<Transform>(entity)
+            .expect("entity has no transform")
+            .translation = position.extend(0.0);
+    }
+
+    /// Run a single frame, then release the per-frame input state.
+    pub fn update(&mut self) {
+        self.app.update();
+        let world = self.app.world_mut();
+        world.resource_mut::<ButtonInput<KeyCode>>().clear();
+        world.resource_mut::<ButtonInput<MouseButton>>().clear();
+    }
+
+    /// Run a number of frames.
+    pub fn update_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.update();
+        }
+    }
+
+    /// Run frames for a duration, in seconds.
+    pub fn update_secs(&mut self, secs: f32) {
+        self.update_frames((secs / FRAME_SECS).round() as usize);
+    }
+
+    pub fn press_key(&mut self, key: KeyCode) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+    }
+
+    pub fn release_key(&mut self, key: KeyCode) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+    }
+
+    pub fn press_mouse(&mut self, button: MouseButton) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(button);
+    }
+
+    pub fn release_mouse(&mut self, button: MouseButton) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(button);
+    }
+
+    /// Move the cursor to a logical position in the window, or out of it if `None`.
+    pub fn set_cursor(&mut self, position: Option<Vec2>) {
+        self.app
+            .world_mut()
+            .get_mut::<Window>(self.window)
+            .expect("window was despawned")
+            .set_cursor_position(position);
+    }
+
+    /// Scroll the mouse wheel by a number of lines, where positive values zoom in.
+    pub fn scroll(&mut self, lines: f32) {
+        let window = self.window;
+        self.app.world_mut().write_message(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.0,
+            y: lines,
+            window,
+        });
+    }
+
+    /// Scroll by a number of pixels, like a trackpad, where positive values zoom in.
+    pub fn scroll_pixels(&mut self, pixels: f32) {
+        let window = self.window;
+        self.app.world_mut().write_message(MouseWheel {
+            unit: MouseScrollUnit::Pixel,
+            x: 0.0,
+            y: pixels,
+            window,
+        });
+    }
+
+    pub fn cam(&self, camera: Entity) -> &OgleCam {
+        self.app
+            .world()
+            .get::<OgleCam>(camera)
+            .expect("entity has no camera")
+    }
+
+    pub fn cam_mut(&mut self, camera: Entity) -> Mut<'_, OgleCam> {
+        self.app
+            .world_mut()
+            .get_mut::<OgleCam>(camera)
+            .expect("entity has no camera")
+    }
+
+    /// The committed translation of a camera.
+    pub fn camera_translation(&self, camera: Entity) -> Vec2 {
+        self.app
+            .world()
+            .get::<Transform>(camera)
+            .expect("entity has no transform")
+            .translation
+            .truncate()
+    }
+
+    /// The committed projection scale of a camera.
+    pub fn camera_scale(&self, camera: Entity) -> f32 {
+        match self.app.world().get::<Projection>(camera) {
+            Some(Projection::Orthographic(projection)) => projection.scale,
+            _ => panic!("entity has no orthographic projection"),
+        }
+    }
+}
+
+/// Assert that two vectors are within a tolerance of each other.
+#[track_caller]
+pub fn assert_near(actual: Vec2, expected: Vec2, tolerance: f32) {
+    assert!(
+        actual.distance(expected) <= tolerance,
+        "expected {expected}, got {actual}"
+    );
+}
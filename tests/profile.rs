@@ -0,0 +1,99 @@
+#![cfg(feature = "profile")]
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_ogle::{prelude::*, OgleCamProfile, OgleProfile};
+use common::TestApp;
+
+/// Spawn a camera with a profile, bounded by an entity's extent.
+fn spawn_profiled_camera(test: &mut TestApp, profile: Handle<OgleProfile>) -> (Entity, Entity) {
+    let level = test.spawn_target(Vec2::ZERO);
+    let mut cam = OgleCam::new(OgleSettings::default(), OgleTarget::None, OgleMode::Frozen);
+    cam.settings.bounds.source = Some(level);
+    let camera = test
+        .app
+        .world_mut()
+        .spawn((cam, OgleCamProfile(profile)))
+        .id();
+    (camera, level)
+}
+
+/// Run frames until the camera has the profile's settings.
+fn wait_for_profile(test: &mut TestApp, camera: Entity) {
+    for _ in 0..100 {
+        if test.cam(camera).settings.zoom_sensitivity == 42.0 {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        test.update();
+    }
+    panic!("profile was never applied");
+}
+
+#[test]
+fn load_ron_profile() {
+    let mut test = TestApp::new();
+    let profile = test
+        .app
+        .world()
+        .resource::<AssetServer>()
+        .load("zoomed.ogle.ron");
+    let (camera, level) = spawn_profiled_camera(&mut test, profile);
+
+    wait_for_profile(&mut test, camera);
+    let bounds = &test.cam(camera).settings.bounds;
+    assert!(bounds.enabled);
+    assert_eq!((bounds.min_x, bounds.max_x), (-500.0, 500.0));
+    // Missing fields keep their defaults, and the camera keeps its bounds source
+    assert_eq!(bounds.min_y, OgleSettings::default().bounds.min_y);
+    assert_eq!(bounds.source, Some(level));
+}
+
+#[test]
+fn load_json_profile() {
+    let mut test = TestApp::new();
+    let profile = test
+        .app
+        .world()
+        .resource::<AssetServer>()
+        .load("zoomed.ogle.json");
+    let (camera, level) = spawn_profiled_camera(&mut test, profile);
+
+    wait_for_profile(&mut test, camera);
+    let bounds = &test.cam(camera).settings.bounds;
+    assert!(bounds.enabled);
+    assert_eq!((bounds.min_x, bounds.max_x), (-500.0, 500.0));
+    assert_eq!(bounds.source, Some(level));
+}
+
+#[test]
+fn modified_profile_is_reapplied() {
+    let mut test = TestApp::new();
+    let profile = test
+        .app
+        .world_mut()
+        .resource_mut::<Assets<OgleProfile>>()
+        .add(OgleProfile {
+            settings: OgleSettings {
+                zoom_sensitivity: 42.0,
+                ..default()
+            },
+        });
+    let (camera, level) = spawn_profiled_camera(&mut test, profile.clone());
+    test.update();
+    assert_eq!(test.cam(camera).settings.zoom_sensitivity, 42.0);
+
+    // Editing the asset, like a hot reload, updates the camera but keeps its bounds source
+    test.app
+        .world_mut()
+        .resource_mut::<Assets<OgleProfile>>()
+        .get_mut(&profile)
+        .expect("profile was removed")
+        .settings
+        .zoom_sensitivity = 7.0;
+    test.update();
+    let settings = &test.cam(camera).settings;
+    assert_eq!(settings.zoom_sensitivity, 7.0);
+    assert_eq!(settings.bounds.source, Some(level));
+}